@@ -0,0 +1,48 @@
+//! Generates the list of days from the `dayN.rs` files in `src`, so that adding a day only takes
+//! adding its file and declaring its module in `lib.rs`, which `new-day` does.
+
+use std::env;
+use std::fs;
+use std::path::Path;
+
+fn main() {
+    let src = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("src");
+    println!("cargo:rerun-if-changed={}", src.display());
+
+    let mut days: Vec<(u32, String)> = fs::read_dir(&src)
+        .unwrap()
+        .filter_map(|entry| {
+            let name = entry.unwrap().file_name().into_string().ok()?;
+            let module = name.strip_suffix(".rs")?;
+            let number = module.strip_prefix("day")?;
+            if number.is_empty() || !number.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+            Some((number.parse().ok()?, module.to_string()))
+        })
+        .collect();
+    days.sort();
+
+    // the modules are declared in lib.rs like any other, so tools that read it can find them
+    let lib = fs::read_to_string(src.join("lib.rs")).unwrap();
+    let mut generated = String::new();
+    for (_, module) in &days {
+        if !lib
+            .lines()
+            .any(|line| line.trim() == format!("pub mod {module};"))
+        {
+            generated += &format!(
+                "compile_error!(\"src/{module}.rs needs `pub mod {module};` in src/lib.rs\");\n"
+            );
+        }
+    }
+    generated += "/// Every day module, with the number from its file name.\n";
+    generated += "pub(crate) static DAY_MODULES: &[(u32, &dyn days::Day)] = &[\n";
+    for (number, module) in &days {
+        generated += &format!("    ({number}, &{module}::Day),\n");
+    }
+    generated += "];\n";
+
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("days.rs");
+    fs::write(out, generated).unwrap();
+}
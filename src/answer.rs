@@ -0,0 +1,212 @@
+use num::BigInt;
+use serde::ser::SerializeSeq;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt::{Display, Formatter};
+
+/// What a solver produces for one part.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Answer {
+    Integer(i64),
+    BigInteger(BigInt),
+    Text(String),
+    /// Shown comma-separated, like day 17's output or day 23's password
+    List(Vec<Answer>),
+    /// Shown as `x,y`
+    Coordinate(i64, i64),
+}
+
+impl Answer {
+    pub fn list<T: Into<Answer>>(items: impl IntoIterator<Item = T>) -> Answer {
+        Answer::List(items.into_iter().map(Into::into).collect())
+    }
+
+    /// An answer typed in by hand: an integer if it looks like one, otherwise text.
+    pub fn parse(text: &str) -> Answer {
+        match text.parse::<BigInt>() {
+            Ok(n) => Answer::from(n),
+            Err(_) => Answer::from(text),
+        }
+    }
+
+    /// The answer as a number, for comparing against "too high" and "too low" hints.
+    pub fn as_i128(&self) -> Option<i128> {
+        match self {
+            Answer::Integer(n) => Some(*n as i128),
+            Answer::BigInteger(n) => i128::try_from(n).ok(),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Integer(n) => write!(f, "{n}"),
+            Answer::BigInteger(n) => write!(f, "{n}"),
+            Answer::Text(text) => write!(f, "{text}"),
+            Answer::List(items) => {
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{item}")?;
+                }
+                Ok(())
+            }
+            Answer::Coordinate(x, y) => write!(f, "{x},{y}"),
+        }
+    }
+}
+
+/// Integers as JSON numbers, except big ones, which most JSON readers would round, as strings.
+impl Serialize for Answer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Answer::Integer(n) => serializer.serialize_i64(*n),
+            Answer::BigInteger(n) => serializer.serialize_str(&n.to_string()),
+            Answer::Text(text) => serializer.serialize_str(text),
+            Answer::List(items) => {
+                let mut seq = serializer.serialize_seq(Some(items.len()))?;
+                for item in items {
+                    seq.serialize_element(item)?;
+                }
+                seq.end()
+            }
+            Answer::Coordinate(x, y) => [x, y].serialize(serializer),
+        }
+    }
+}
+
+/// Reads back what [`Serialize`] writes, as far as it can tell: big integers come back as text and
+/// coordinates as lists, which show the same.
+impl<'de> Deserialize<'de> for Answer {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Answer, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Json {
+            Integer(i64),
+            Text(String),
+            List(Vec<Answer>),
+        }
+        Ok(match Json::deserialize(deserializer)? {
+            Json::Integer(n) => Answer::Integer(n),
+            Json::Text(text) => Answer::Text(text),
+            Json::List(items) => Answer::List(items),
+        })
+    }
+}
+
+macro_rules! from_integer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Answer {
+                    match i64::try_from(n) {
+                        Ok(n) => Answer::Integer(n),
+                        Err(_) => Answer::BigInteger(BigInt::from(n)),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+from_integer!(i32, u32, i64, u64, i128, u128, usize);
+
+impl From<BigInt> for Answer {
+    fn from(n: BigInt) -> Answer {
+        match i64::try_from(&n) {
+            Ok(n) => Answer::Integer(n),
+            Err(_) => Answer::BigInteger(n),
+        }
+    }
+}
+
+impl From<String> for Answer {
+    fn from(text: String) -> Answer {
+        Answer::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Answer {
+        Answer::Text(text.to_string())
+    }
+}
+
+/// Compares the answer as it would be shown, which is how stored answers are kept.
+impl PartialEq<str> for Answer {
+    fn eq(&self, other: &str) -> bool {
+        self.to_string().as_str() == other
+    }
+}
+
+impl PartialEq<&str> for Answer {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+
+impl PartialEq<String> for Answer {
+    fn eq(&self, other: &String) -> bool {
+        self == other.as_str()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        assert_eq!(Answer::from(1928).to_string(), "1928");
+        assert_eq!(Answer::from(u64::MAX).to_string(), "18446744073709551615");
+        assert_eq!(Answer::list([4, 6, 3]).to_string(), "4,6,3");
+        assert_eq!(Answer::list(["co", "de", "ka"]).to_string(), "co,de,ka");
+        assert_eq!(Answer::Coordinate(6, 1).to_string(), "6,1");
+    }
+    #[test]
+    fn integers() {
+        assert_eq!(Answer::from(7usize), Answer::Integer(7));
+        assert_eq!(Answer::from(BigInt::from(7)), Answer::Integer(7));
+        assert!(matches!(Answer::from(u64::MAX), Answer::BigInteger(_)));
+        assert_eq!(Answer::from(u64::MAX).as_i128(), Some(u64::MAX as i128));
+        assert_eq!(Answer::from("7").as_i128(), None);
+    }
+    #[test]
+    fn parse() {
+        assert_eq!(Answer::parse("-17"), Answer::Integer(-17));
+        assert!(matches!(
+            Answer::parse("18446744073709551615"),
+            Answer::BigInteger(_)
+        ));
+        assert_eq!(Answer::parse("co,de"), Answer::Text("co,de".to_string()));
+    }
+    #[test]
+    fn compares_as_shown() {
+        assert_eq!(Answer::from(1928), "1928");
+        assert_eq!(Answer::list([4, 6, 3]), "4,6,3".to_string());
+        assert_ne!(Answer::Coordinate(6, 1), "1,6");
+    }
+    #[test]
+    fn json() {
+        let json = |answer: Answer| serde_json::to_string(&answer).unwrap();
+        assert_eq!(json(Answer::from(1928)), "1928");
+        assert_eq!(json(Answer::from(u64::MAX)), r#""18446744073709551615""#);
+        assert_eq!(json(Answer::from("co,de")), r#""co,de""#);
+        assert_eq!(json(Answer::list([4, 6, 3])), "[4,6,3]");
+        assert_eq!(json(Answer::Coordinate(6, 1)), "[6,1]");
+    }
+    #[test]
+    fn from_json() {
+        let answer = |json| serde_json::from_str::<Answer>(json).unwrap();
+        assert_eq!(answer("1928"), Answer::Integer(1928));
+        assert_eq!(answer(r#""co,de""#), Answer::from("co,de"));
+        assert_eq!(answer("[4,6,3]"), Answer::list([4, 6, 3]));
+        assert_eq!(
+            answer(r#""18446744073709551615""#),
+            Answer::from(u64::MAX).to_string()
+        );
+        assert_eq!(answer("[6,1]"), Answer::Coordinate(6, 1).to_string());
+    }
+}
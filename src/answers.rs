@@ -0,0 +1,256 @@
+use crate::days::{Answer, AoCError};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io::ErrorKind;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub enum Hint {
+    #[serde(rename = "too high")]
+    TooHigh,
+    #[serde(rename = "too low")]
+    TooLow,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct WrongAnswer {
+    #[serde(deserialize_with = "string_or_integer")]
+    pub answer: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hint: Option<Hint>,
+}
+
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PartAnswers {
+    #[serde(
+        default,
+        deserialize_with = "optional_string_or_integer",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub answer: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub wrong: Vec<WrongAnswer>,
+}
+
+/// The contents of `answers/dayN.toml`, e.g.
+///
+/// ```toml
+/// [part1]
+/// answer = 1928
+///
+/// [part2]
+/// wrong = [{ answer = 6434671719741, hint = "too high" }]
+/// ```
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct DayAnswers {
+    #[serde(default)]
+    pub part1: PartAnswers,
+    #[serde(default)]
+    pub part2: PartAnswers,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    /// Matches the confirmed answer
+    Correct,
+    /// Differs from the confirmed answer
+    Incorrect,
+    /// Has already been rejected
+    KnownWrong,
+    /// Is outside a "too high" or "too low" bound from a rejected answer
+    ViolatesBound,
+    /// Nothing is known about this part yet
+    Unknown,
+}
+
+impl Verdict {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::Incorrect => "incorrect",
+            Verdict::KnownWrong => "known_wrong",
+            Verdict::ViolatesBound => "violates_bound",
+            Verdict::Unknown => "unknown",
+        }
+    }
+
+    pub fn is_failure(&self) -> bool {
+        matches!(
+            self,
+            Verdict::Incorrect | Verdict::KnownWrong | Verdict::ViolatesBound
+        )
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Verdict::Correct => "correct",
+            Verdict::Incorrect => "INCORRECT",
+            Verdict::KnownWrong => "KNOWN WRONG",
+            Verdict::ViolatesBound => "OUT OF BOUNDS",
+            Verdict::Unknown => "unknown",
+        })
+    }
+}
+
+fn string_or_integer<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Value {
+        String(String),
+        Integer(i64),
+    }
+    Ok(match Value::deserialize(deserializer)? {
+        Value::String(s) => s,
+        Value::Integer(i) => i.to_string(),
+    })
+}
+
+fn optional_string_or_integer<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<String>, D::Error> {
+    string_or_integer(deserializer).map(Some)
+}
+
+impl PartAnswers {
+    pub fn verify(&self, answer: &Answer) -> Verdict {
+        if let Some(expected) = &self.answer {
+            return if answer == expected {
+                Verdict::Correct
+            } else {
+                Verdict::Incorrect
+            };
+        }
+
+        if self.wrong.iter().any(|wrong| answer == &wrong.answer) {
+            return Verdict::KnownWrong;
+        }
+
+        if let Some(value) = answer.as_i128() {
+            let out_of_bounds =
+                self.wrong
+                    .iter()
+                    .any(|wrong| match (wrong.hint, wrong.answer.parse::<i128>()) {
+                        (Some(Hint::TooHigh), Ok(bound)) => value >= bound,
+                        (Some(Hint::TooLow), Ok(bound)) => value <= bound,
+                        _ => false,
+                    });
+            if out_of_bounds {
+                return Verdict::ViolatesBound;
+            }
+        }
+
+        Verdict::Unknown
+    }
+}
+
+impl DayAnswers {
+    pub fn part(&self, part: u32) -> &PartAnswers {
+        match part {
+            1 => &self.part1,
+            _ => &self.part2,
+        }
+    }
+
+    pub fn part_mut(&mut self, part: u32) -> &mut PartAnswers {
+        match part {
+            1 => &mut self.part1,
+            _ => &mut self.part2,
+        }
+    }
+}
+
+pub fn answers_filename(dir: &str, day: u32) -> String {
+    format!("{dir}/day{day}.toml")
+}
+
+pub fn load(dir: &str, day: u32) -> Result<DayAnswers, AoCError> {
+    load_file(&answers_filename(dir, day))
+}
+
+/// Loads answers from any file in the `answers/dayN.toml` format, or none if it does not exist.
+pub fn load_file(filename: &str) -> Result<DayAnswers, AoCError> {
+    match fs::read_to_string(filename) {
+        Ok(content) => {
+            toml::from_str(&content).map_err(|e| AoCError::AnswerFile(filename.to_string(), e))
+        }
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(DayAnswers::default()),
+        Err(e) => Err(AoCError::from(e)),
+    }
+}
+
+pub fn save(dir: &str, day: u32, answers: &DayAnswers) -> Result<(), AoCError> {
+    fs::create_dir_all(dir)?;
+    fs::write(
+        answers_filename(dir, day),
+        toml::to_string(answers).unwrap(),
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOML: &str = r#"
+[part1]
+answer = 1928
+
+[part2]
+wrong = [
+    { answer = "6434671719741", hint = "too high" },
+    { answer = 100, hint = "too low" },
+    { answer = "abc" },
+]
+"#;
+
+    #[test]
+    fn parse() {
+        let answers: DayAnswers = toml::from_str(TOML).unwrap();
+        assert_eq!(answers.part1.answer, Some("1928".to_string()));
+        assert_eq!(answers.part2.answer, None);
+        assert_eq!(
+            answers.part2.wrong[0],
+            WrongAnswer {
+                answer: "6434671719741".to_string(),
+                hint: Some(Hint::TooHigh)
+            }
+        );
+        assert_eq!(answers.part2.wrong[1].answer, "100");
+        assert_eq!(answers.part2.wrong[2].hint, None);
+    }
+    #[test]
+    fn parse_empty() {
+        let answers: DayAnswers = toml::from_str("").unwrap();
+        assert_eq!(answers, DayAnswers::default());
+    }
+    #[test]
+    fn round_trip() {
+        let answers: DayAnswers = toml::from_str(TOML).unwrap();
+        let text = toml::to_string(&answers).unwrap();
+        assert_eq!(toml::from_str::<DayAnswers>(&text).unwrap(), answers);
+    }
+    #[test]
+    fn verify() {
+        let answers: DayAnswers = toml::from_str(TOML).unwrap();
+        let verify = |part, answer| answers.part(part).verify(&Answer::parse(answer));
+        assert_eq!(verify(1, "1928"), Verdict::Correct);
+        assert_eq!(verify(1, "1929"), Verdict::Incorrect);
+        assert_eq!(verify(2, "6434671719741"), Verdict::KnownWrong);
+        assert_eq!(verify(2, "abc"), Verdict::KnownWrong);
+        assert_eq!(verify(2, "7000000000000"), Verdict::ViolatesBound);
+        assert_eq!(verify(2, "50"), Verdict::ViolatesBound);
+        assert_eq!(verify(2, "6418529470362"), Verdict::Unknown);
+        assert_eq!(verify(2, "xyz"), Verdict::Unknown);
+    }
+    #[test]
+    fn failures() {
+        assert!(!Verdict::Correct.is_failure());
+        assert!(!Verdict::Unknown.is_failure());
+        assert!(Verdict::Incorrect.is_failure());
+        assert!(Verdict::KnownWrong.is_failure());
+        assert!(Verdict::ViolatesBound.is_failure());
+    }
+}
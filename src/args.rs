@@ -0,0 +1,101 @@
+//! Parsing for the days and durations given on the command line or in the config file.
+
+use std::collections::BTreeSet;
+use std::time::Duration;
+
+pub const DAYS: std::ops::RangeInclusive<u32> = 1..=25;
+
+pub fn parse_day(s: &str) -> Result<u32, String> {
+    let day = s
+        .trim()
+        .parse::<u32>()
+        .map_err(|_| format!("'{s}' is not a day number"))?;
+    if DAYS.contains(&day) {
+        Ok(day)
+    } else {
+        Err(format!("day {day} is not between 1 and 25"))
+    }
+}
+
+pub fn parse_days(s: &str) -> Result<BTreeSet<u32>, String> {
+    let mut result = BTreeSet::new();
+
+    for item in s.split(',') {
+        match item.split_once('-') {
+            Some((from, to)) => {
+                let from = parse_day(from)?;
+                let to = parse_day(to)?;
+                if from > to {
+                    return Err(format!("day range '{item}' is backwards"));
+                }
+                result.extend(from..=to);
+            }
+            None => {
+                result.insert(parse_day(item)?);
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+pub fn parse_duration(s: &str) -> Result<Duration, String> {
+    let s = s.trim();
+    let split = s
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(s.len());
+    let (value, unit) = s.split_at(split);
+    let value = value
+        .parse::<f64>()
+        .map_err(|_| format!("'{s}' is not a duration"))?;
+
+    let seconds = match unit {
+        "ns" => value / 1e9,
+        "us" | "µs" => value / 1e6,
+        "ms" => value / 1e3,
+        "s" | "" => value,
+        "m" => value * 60.0,
+        _ => return Err(format!("unknown duration unit '{unit}'")),
+    };
+
+    Duration::try_from_secs_f64(seconds).map_err(|e| format!("'{s}': {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_days_single() {
+        assert_eq!(parse_days("17"), Ok(BTreeSet::from([17])));
+    }
+    #[test]
+    fn parse_days_ranges_and_singles() {
+        assert_eq!(
+            parse_days("1-5,17"),
+            Ok(BTreeSet::from([1, 2, 3, 4, 5, 17]))
+        );
+    }
+    #[test]
+    fn parse_days_overlapping() {
+        assert_eq!(parse_days("3-4,2-3"), Ok(BTreeSet::from([2, 3, 4])));
+    }
+    #[test]
+    fn parse_days_invalid() {
+        assert!(parse_days("0").is_err());
+        assert!(parse_days("26").is_err());
+        assert!(parse_days("5-1").is_err());
+        assert!(parse_days("x").is_err());
+        assert!(parse_days("").is_err());
+    }
+    #[test]
+    fn parse_durations() {
+        assert_eq!(parse_duration("500ms"), Ok(Duration::from_millis(500)));
+        assert_eq!(parse_duration("2s"), Ok(Duration::from_secs(2)));
+        assert_eq!(parse_duration("1.5"), Ok(Duration::from_millis(1500)));
+        assert_eq!(parse_duration("250us"), Ok(Duration::from_micros(250)));
+        assert!(parse_duration("fast").is_err());
+        assert!(parse_duration("3h").is_err());
+        assert!(parse_duration("99999999999999999999m").is_err());
+    }
+}
@@ -0,0 +1,216 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Copy, Clone)]
+pub struct BenchOptions {
+    pub warmup: usize,
+    pub runs: usize,
+    pub budget: Option<Duration>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Stats {
+    pub runs: usize,
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub mean_ns: u64,
+    pub p95_ns: u64,
+    pub std_dev_ns: u64,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Option<Stats> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut nanos: Vec<u64> = samples.iter().map(|d| d.as_nanos() as u64).collect();
+        nanos.sort();
+
+        let n = nanos.len();
+        let median = if n.is_multiple_of(2) {
+            (nanos[n / 2 - 1] + nanos[n / 2]) / 2
+        } else {
+            nanos[n / 2]
+        };
+        // nearest-rank percentile
+        let p95 = nanos[(n * 95).div_ceil(100) - 1];
+
+        let mean = nanos.iter().map(|&s| s as f64).sum::<f64>() / n as f64;
+        let variance = nanos
+            .iter()
+            .map(|&s| (s as f64 - mean).powi(2))
+            .sum::<f64>()
+            / n as f64;
+
+        Some(Stats {
+            runs: n,
+            min_ns: nanos[0],
+            median_ns: median,
+            mean_ns: mean.round() as u64,
+            p95_ns: p95,
+            std_dev_ns: variance.sqrt().round() as u64,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BenchResult {
+    pub day: u32,
+    pub part: u32,
+    pub answer: Option<String>,
+    pub stats: Stats,
+}
+
+/// Runs `f` `options.warmup` times untimed, then either `options.runs` times or, when a budget
+/// is given, until the budget has been spent. Returns the last answer and the timing stats.
+pub fn bench<A, F>(options: &BenchOptions, f: F) -> (Option<A>, Stats)
+where
+    F: Fn() -> Option<A>,
+{
+    for _ in 0..options.warmup {
+        f();
+    }
+
+    let mut samples = vec![];
+    let mut answer = None;
+    let started = Instant::now();
+
+    loop {
+        let start = Instant::now();
+        answer = f().or(answer);
+        samples.push(Instant::now().duration_since(start));
+
+        let done = match options.budget {
+            Some(budget) => started.elapsed() >= budget,
+            None => samples.len() >= options.runs,
+        };
+        if done {
+            break;
+        }
+    }
+
+    (answer, Stats::from_samples(&samples).unwrap())
+}
+
+pub fn load_baseline(path: &str) -> Result<Vec<BenchResult>, String> {
+    let content = fs::read_to_string(path).map_err(|e| format!("could not read {path}: {e}"))?;
+    serde_json::from_str(&content).map_err(|e| format!("could not parse {path}: {e}"))
+}
+
+pub fn save_baseline(path: &str, results: &[BenchResult]) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(results).unwrap();
+    fs::write(path, content).map_err(|e| format!("could not write {path}: {e}"))
+}
+
+/// Relative change in median time against the baseline, e.g. `0.25` for 25% slower.
+pub fn change_from(baseline: &[BenchResult], result: &BenchResult) -> Option<f64> {
+    baseline
+        .iter()
+        .find(|b| b.day == result.day && b.part == result.part)
+        .filter(|b| b.stats.median_ns > 0)
+        .map(|b| {
+            (result.stats.median_ns as f64 - b.stats.median_ns as f64) / b.stats.median_ns as f64
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    fn ns(nanos: &[u64]) -> Vec<Duration> {
+        nanos.iter().map(|&n| Duration::from_nanos(n)).collect()
+    }
+
+    #[test]
+    fn stats_odd() {
+        assert_eq!(
+            Stats::from_samples(&ns(&[5, 1, 3])),
+            Some(Stats {
+                runs: 3,
+                min_ns: 1,
+                median_ns: 3,
+                mean_ns: 3,
+                p95_ns: 5,
+                std_dev_ns: 2,
+            })
+        );
+    }
+    #[test]
+    fn stats_even() {
+        let stats = Stats::from_samples(&ns(&[10, 40, 20, 30])).unwrap();
+        assert_eq!(stats.median_ns, 25);
+        assert_eq!(stats.mean_ns, 25);
+        assert_eq!(stats.p95_ns, 40);
+        assert_eq!(stats.std_dev_ns, 11);
+    }
+    #[test]
+    fn stats_p95() {
+        let samples = ns(&(1..=100).collect::<Vec<_>>());
+        assert_eq!(Stats::from_samples(&samples).unwrap().p95_ns, 95);
+    }
+    #[test]
+    fn stats_empty() {
+        assert_eq!(Stats::from_samples(&[]), None);
+    }
+    #[test]
+    fn bench_runs_warmup_and_runs() {
+        let calls = Cell::new(0);
+        let options = BenchOptions {
+            warmup: 2,
+            runs: 5,
+            budget: None,
+        };
+        let (answer, stats) = bench(&options, || {
+            calls.set(calls.get() + 1);
+            Some("42".to_string())
+        });
+        assert_eq!(calls.get(), 7);
+        assert_eq!(stats.runs, 5);
+        assert_eq!(answer, Some("42".to_string()));
+    }
+    #[test]
+    fn bench_budget() {
+        let options = BenchOptions {
+            warmup: 0,
+            runs: 1,
+            budget: Some(Duration::from_millis(20)),
+        };
+        let (_, stats) = bench(&options, || {
+            std::thread::sleep(Duration::from_millis(5));
+            None::<u64>
+        });
+        assert!(stats.runs >= 2);
+    }
+    #[test]
+    fn change() {
+        let result = |median_ns| BenchResult {
+            day: 1,
+            part: 2,
+            answer: None,
+            stats: Stats {
+                runs: 1,
+                min_ns: median_ns,
+                median_ns,
+                mean_ns: median_ns,
+                p95_ns: median_ns,
+                std_dev_ns: 0,
+            },
+        };
+        let baseline = vec![result(100)];
+        assert_eq!(change_from(&baseline, &result(125)), Some(0.25));
+        assert_eq!(change_from(&baseline, &result(50)), Some(-0.5));
+        assert_eq!(
+            change_from(
+                &baseline,
+                &BenchResult {
+                    part: 1,
+                    ..result(100)
+                }
+            ),
+            None
+        );
+    }
+}
@@ -0,0 +1,122 @@
+use crate::days::AoCError;
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+/// Large enough for the recursive solvers, which used to run on the main thread.
+const STACK_SIZE: usize = 64 * 1024 * 1024;
+
+/// Shared flag telling a solver that the runner has stopped waiting for it.
+#[derive(Debug, Clone, Default)]
+pub struct Token(Arc<AtomicBool>);
+
+impl Token {
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+thread_local! {
+    static CURRENT: RefCell<Token> = RefCell::new(Token::default());
+}
+
+/// Whether the runner has given up on the part being solved on this thread.
+pub fn is_cancelled() -> bool {
+    CURRENT.with(|token| token.borrow().is_cancelled())
+}
+
+/// Fails once the runner has given up on this thread's part, so long searches can stop with `?`.
+pub fn check() -> Result<(), AoCError> {
+    if is_cancelled() {
+        Err(AoCError::Cancelled)
+    } else {
+        Ok(())
+    }
+}
+
+/// Runs `f` on a thread of its own, waiting at most `limit` for it. On timeout the thread's
+/// token is cancelled and it is left to finish, or notice, in the background.
+pub fn run<R, F>(limit: Option<Duration>, f: F) -> Result<R, AoCError>
+where
+    R: Send + 'static,
+    F: FnOnce() -> R + Send + 'static,
+{
+    let token = Token::default();
+    let (sender, receiver) = mpsc::channel();
+
+    let thread_token = token.clone();
+    let handle = thread::Builder::new()
+        .stack_size(STACK_SIZE)
+        .spawn(move || {
+            CURRENT.with(|current| *current.borrow_mut() = thread_token);
+            // the runner may have stopped listening
+            let _ = sender.send(f());
+        })?;
+
+    let received = match limit {
+        Some(limit) => receiver.recv_timeout(limit),
+        None => receiver.recv().map_err(RecvTimeoutError::from),
+    };
+    match received {
+        Ok(result) => Ok(result),
+        Err(RecvTimeoutError::Timeout) => {
+            token.cancel();
+            Err(AoCError::Timeout(limit.unwrap_or_default()))
+        }
+        Err(RecvTimeoutError::Disconnected) => match handle.join() {
+            Err(payload) => std::panic::resume_unwind(payload),
+            Ok(()) => unreachable!("solver thread finished without sending a result"),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    #[test]
+    fn finishes_within_limit() {
+        assert_eq!(run(Some(Duration::from_secs(5)), || 42).unwrap(), 42);
+        assert_eq!(run(None, || 42).unwrap(), 42);
+    }
+    #[test]
+    fn times_out() {
+        let start = Instant::now();
+        let result = run(Some(Duration::from_millis(20)), || {
+            thread::sleep(Duration::from_secs(5));
+        });
+        assert!(matches!(result, Err(AoCError::Timeout(_))));
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+    #[test]
+    fn solver_sees_cancellation() {
+        let (sender, receiver) = mpsc::channel();
+        let result = run(Some(Duration::from_millis(20)), move || {
+            let outcome = loop {
+                if let Err(e) = check() {
+                    break e;
+                }
+                thread::sleep(Duration::from_millis(1));
+            };
+            sender.send(outcome.to_string()).unwrap();
+        });
+        assert!(matches!(result, Err(AoCError::Timeout(_))));
+        assert_eq!(
+            receiver.recv_timeout(Duration::from_secs(5)).unwrap(),
+            "cancelled"
+        );
+    }
+    #[test]
+    fn other_threads_are_not_cancelled() {
+        assert!(!is_cancelled());
+        assert!(check().is_ok());
+    }
+}
@@ -0,0 +1,295 @@
+use advent_of_code_2024::args::{parse_days, parse_duration};
+use advent_of_code_2024::output::Format;
+use advent_of_code_2024::parallel;
+use advent_of_code_2024::runner::Selection;
+use clap::{Parser, Subcommand};
+use std::collections::BTreeSet;
+use std::time::Duration;
+
+#[derive(Parser, Debug)]
+#[command(about = "Advent of Code 2024 solutions")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Config file to use instead of `aoc.toml`
+    #[arg(long, global = true)]
+    pub config: Option<String>,
+
+    /// Days to run, e.g. `17` or `1-5,17`
+    #[arg(short, long, value_parser = parse_days, conflicts_with = "all")]
+    pub day: Option<BTreeSet<u32>>,
+
+    /// Only run this part
+    #[arg(short, long, value_parser = clap::value_parser!(u32).range(1..=2))]
+    pub part: Option<u32>,
+
+    /// Run every day (the default when no days are given)
+    #[arg(short, long)]
+    pub all: bool,
+
+    /// List the days and the parts each one implements, without running them
+    #[arg(short, long)]
+    pub list: bool,
+
+    /// Wait for the next puzzle (or the single `--day` given) to unlock, then fetch and run it
+    #[arg(short, long)]
+    pub wait: bool,
+
+    /// How to print the results
+    #[arg(short, long, value_enum, default_value_t)]
+    pub format: Format,
+
+    /// Solve this file instead of the day's puzzle input: `-` for stdin, or a directory to solve
+    /// every `*.txt` file in it, checking each against a `.toml` answers file beside it
+    #[arg(short, long, conflicts_with_all = ["bench", "wait"])]
+    pub input: Option<String>,
+
+    /// Solve the examples in `examples/dayN/` instead of the puzzle inputs, checking the answers
+    /// they list
+    #[arg(short, long, conflicts_with_all = ["bench", "wait", "input"])]
+    pub examples: bool,
+
+    /// Set a puzzle parameter for the days that have it, e.g. `blinks=40`; `--list` shows them
+    #[arg(long = "param", value_name = "NAME=VALUE", value_parser = parse_param)]
+    pub params: Vec<(String, String)>,
+
+    /// Solve parts on this many threads, or one per CPU for `0`
+    #[arg(short, long, default_value_t = 1, conflicts_with = "bench")]
+    pub jobs: usize,
+
+    /// Give up on a part after this long (e.g. `30s`), unless its day has its own limit in the config
+    #[arg(long, value_parser = parse_duration, conflicts_with = "bench")]
+    pub timeout: Option<Duration>,
+
+    /// Also write the results to this HTML file, with their trend over past runs
+    #[arg(long, value_name = "FILE", conflicts_with = "bench")]
+    pub report: Option<String>,
+
+    /// Benchmark each part over repeated runs instead of running it once
+    #[arg(short, long)]
+    pub bench: bool,
+
+    /// Untimed runs before benchmarking
+    #[arg(long, default_value_t = 1, requires = "bench")]
+    pub warmup: usize,
+
+    /// Timed runs per part when benchmarking
+    #[arg(long, default_value_t = 10, requires = "bench")]
+    pub runs: usize,
+
+    /// Benchmark each part for this long (e.g. `500ms`, `2s`) instead of a fixed number of runs
+    #[arg(long, value_parser = parse_duration, requires = "bench")]
+    pub budget: Option<Duration>,
+
+    /// Compare benchmark medians against this baseline file
+    #[arg(long, requires = "bench")]
+    pub baseline: Option<String>,
+
+    /// Write the benchmark results to this baseline file
+    #[arg(long, requires = "bench")]
+    pub save_baseline: Option<String>,
+
+    /// Percentage slowdown against the baseline that counts as a regression, failing the run
+    #[arg(long, default_value_t = 10.0, requires = "baseline")]
+    pub threshold: f64,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Submit an answer, unless it is already known to be wrong
+    Submit {
+        #[arg(value_parser = clap::value_parser!(u32).range(1..=25))]
+        day: u32,
+        #[arg(value_parser = clap::value_parser!(u32).range(1..=2))]
+        part: u32,
+        answer: String,
+        /// Where to submit to, e.g. a local stand-in server for testing
+        #[arg(long)]
+        base_url: Option<String>,
+    },
+    /// Create a module for a new day from `src/day.rs.template`
+    NewDay {
+        #[arg(value_parser = clap::value_parser!(u32).range(1..=25))]
+        day: u32,
+        /// Example files to copy into `examples/dayN/`: `part1: <answer>` and `part2: <answer>`
+        /// lines, a `---` line, then the input
+        #[arg(long)]
+        example: Vec<String>,
+        /// An example input to add to `examples/dayN/`, alongside `--part1` and/or `--part2`
+        #[arg(long)]
+        input: Option<String>,
+        /// The part 1 answer for `--input`
+        #[arg(long, requires = "input")]
+        part1: Option<String>,
+        /// The part 2 answer for `--input`
+        #[arg(long, requires = "input")]
+        part2: Option<String>,
+    },
+    /// Rebuild and re-run a day whenever the source, its input, answers or examples change: the
+    /// examples first, then the puzzle input once they pass
+    Watch {
+        #[arg(value_parser = clap::value_parser!(u32).range(1..=25))]
+        day: u32,
+        /// How often to check the files for changes
+        #[arg(long, default_value = "500ms", value_parser = parse_duration)]
+        interval: Duration,
+    },
+}
+
+impl Cli {
+    pub fn wants_day(&self, day: u32) -> bool {
+        self.day
+            .as_ref()
+            .map(|days| days.contains(&day))
+            .unwrap_or(true)
+    }
+
+    /// The parts to run, their parameters and how to print them.
+    pub fn selection(&self) -> Selection {
+        Selection {
+            part: self.part,
+            params: self.params.iter().cloned().collect(),
+            format: self.format,
+        }
+    }
+
+    pub fn workers(&self) -> usize {
+        match self.jobs {
+            0 => parallel::default_workers(),
+            jobs => jobs,
+        }
+    }
+}
+
+fn parse_param(s: &str) -> Result<(String, String), String> {
+    match s.split_once('=') {
+        Some((name, value)) if !name.trim().is_empty() => {
+            Ok((name.trim().to_string(), value.trim().to_string()))
+        }
+        _ => Err(format!("'{s}' is not <name>=<value>")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use advent_of_code_2024::args::DAYS;
+
+    #[test]
+    fn durations() {
+        let cli = Cli::parse_from(["aoc", "--bench", "--budget", "500ms"]);
+        assert_eq!(cli.budget, Some(Duration::from_millis(500)));
+        let budget = ["aoc", "--bench", "--budget", "99999999999999999999m"];
+        assert!(Cli::try_parse_from(budget).is_err());
+    }
+    #[test]
+    fn bench_options_require_bench() {
+        assert!(Cli::try_parse_from(["aoc", "--runs", "5"]).is_err());
+        assert!(Cli::try_parse_from(["aoc", "--bench", "--runs", "5"]).is_ok());
+    }
+    #[test]
+    fn day_and_part_selection() {
+        let cli = Cli::parse_from(["aoc", "--day", "17", "--part", "2"]);
+        assert!(cli.wants_day(17));
+        assert!(!cli.wants_day(16));
+        assert!(cli.selection().wants_part(2));
+        assert!(!cli.selection().wants_part(1));
+    }
+    #[test]
+    fn default_selects_everything() {
+        let cli = Cli::parse_from(["aoc"]);
+        assert!(DAYS.clone().all(|day| cli.wants_day(day)));
+        assert!(cli.selection().wants_part(1) && cli.selection().wants_part(2));
+    }
+    #[test]
+    fn submit_command() {
+        let cli = Cli::parse_from(["aoc", "submit", "9", "2", "6418529470362"]);
+        match cli.command {
+            Some(Command::Submit {
+                day,
+                part,
+                answer,
+                base_url,
+            }) => {
+                assert_eq!((day, part, answer.as_str()), (9, 2, "6418529470362"));
+                assert_eq!(base_url, None);
+            }
+            _ => panic!("expected submit command"),
+        }
+        assert!(Cli::try_parse_from(["aoc", "submit", "9", "3", "1"]).is_err());
+    }
+    #[test]
+    fn jobs() {
+        assert_eq!(Cli::parse_from(["aoc"]).workers(), 1);
+        assert_eq!(Cli::parse_from(["aoc", "--jobs", "4"]).workers(), 4);
+        assert!(Cli::parse_from(["aoc", "-j", "0"]).workers() >= 1);
+        assert!(Cli::try_parse_from(["aoc", "--jobs", "4", "--bench"]).is_err());
+    }
+    #[test]
+    fn new_day_command() {
+        let cli = Cli::parse_from([
+            "aoc",
+            "new-day",
+            "3",
+            "--input",
+            "xmul(2,4)",
+            "--part1",
+            "8",
+            "--example",
+            "e.txt",
+        ]);
+        match cli.command {
+            Some(Command::NewDay {
+                day,
+                example,
+                input,
+                part1,
+                part2,
+            }) => {
+                assert_eq!(day, 3);
+                assert_eq!(example, ["e.txt"]);
+                assert_eq!(input.as_deref(), Some("xmul(2,4)"));
+                assert_eq!((part1.as_deref(), part2), (Some("8"), None));
+            }
+            _ => panic!("expected new-day command"),
+        }
+        assert!(Cli::try_parse_from(["aoc", "new-day", "3", "--part1", "8"]).is_err());
+    }
+    #[test]
+    fn watch_command() {
+        let cli = Cli::parse_from(["aoc", "--param", "threshold=50", "watch", "20"]);
+        assert_eq!(cli.params.len(), 1);
+        match cli.command {
+            Some(Command::Watch { day, interval }) => {
+                assert_eq!(day, 20);
+                assert_eq!(interval, Duration::from_millis(500));
+            }
+            _ => panic!("expected watch command"),
+        }
+        assert!(Cli::try_parse_from(["aoc", "watch", "26"]).is_err());
+    }
+    #[test]
+    fn params() {
+        let cli = Cli::parse_from(["aoc", "--param", "blinks=40", "--param", "width = 11"]);
+        assert_eq!(
+            cli.params,
+            [
+                ("blinks".to_string(), "40".to_string()),
+                ("width".to_string(), "11".to_string())
+            ]
+        );
+        assert!(Cli::try_parse_from(["aoc", "--param", "blinks"]).is_err());
+        assert!(Cli::try_parse_from(["aoc", "--param", "=40"]).is_err());
+    }
+    #[test]
+    fn examples() {
+        assert!(Cli::parse_from(["aoc", "--examples", "-d", "20"]).examples);
+        assert!(Cli::try_parse_from(["aoc", "-e", "--input", "day20.txt"]).is_err());
+        assert!(Cli::try_parse_from(["aoc", "-e", "--bench"]).is_err());
+    }
+    #[test]
+    fn all_conflicts_with_day() {
+        assert!(Cli::try_parse_from(["aoc", "--all", "--day", "1"]).is_err());
+    }
+}
@@ -0,0 +1,247 @@
+use enumset::EnumSetType;
+use nalgebra::Vector2;
+use std::ops::{Add, Sub};
+
+/// A position on a map, `x` to the right and `y` down, signed so a step off the edge can be
+/// represented.
+pub type Point = Vector2<i64>;
+
+/// One of the four orthogonal directions on a map, with up being towards the first line.
+#[derive(Debug, Hash, PartialOrd, Ord, EnumSetType)]
+pub enum Dir4 {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Dir4 {
+    /// Clockwise from up.
+    pub const ALL: [Dir4; 4] = [Dir4::Up, Dir4::Right, Dir4::Down, Dir4::Left];
+
+    /// The step it makes as `(dx, dy)`.
+    pub fn offset(self) -> (i64, i64) {
+        match self {
+            Dir4::Up => (0, -1),
+            Dir4::Right => (1, 0),
+            Dir4::Down => (0, 1),
+            Dir4::Left => (-1, 0),
+        }
+    }
+
+    pub fn turn_right(self) -> Dir4 {
+        Dir4::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Dir4 {
+        Dir4::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn reverse(self) -> Dir4 {
+        Dir4::ALL[(self as usize + 2) % 4]
+    }
+
+    /// The two directions at right angles to this one.
+    pub fn perpendicular(self) -> [Dir4; 2] {
+        [self.turn_left(), self.turn_right()]
+    }
+
+    /// Reads the arrows the puzzles use for moves: `^`, `>`, `v` and `<`.
+    pub fn from_arrow(c: char) -> Option<Dir4> {
+        match c {
+            '^' => Some(Dir4::Up),
+            '>' => Some(Dir4::Right),
+            'v' => Some(Dir4::Down),
+            '<' => Some(Dir4::Left),
+            _ => None,
+        }
+    }
+
+    pub fn arrow(self) -> char {
+        match self {
+            Dir4::Up => '^',
+            Dir4::Right => '>',
+            Dir4::Down => 'v',
+            Dir4::Left => '<',
+        }
+    }
+}
+
+/// One of the eight directions on a map, including diagonals.
+#[derive(Debug, Hash, PartialOrd, Ord, EnumSetType)]
+pub enum Dir8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Dir8 {
+    /// Clockwise from up.
+    pub const ALL: [Dir8; 8] = [
+        Dir8::Up,
+        Dir8::UpRight,
+        Dir8::Right,
+        Dir8::DownRight,
+        Dir8::Down,
+        Dir8::DownLeft,
+        Dir8::Left,
+        Dir8::UpLeft,
+    ];
+
+    /// The step it makes as `(dx, dy)`.
+    pub fn offset(self) -> (i64, i64) {
+        match self {
+            Dir8::Up => (0, -1),
+            Dir8::UpRight => (1, -1),
+            Dir8::Right => (1, 0),
+            Dir8::DownRight => (1, 1),
+            Dir8::Down => (0, 1),
+            Dir8::DownLeft => (-1, 1),
+            Dir8::Left => (-1, 0),
+            Dir8::UpLeft => (-1, -1),
+        }
+    }
+
+    /// An eighth of a turn clockwise.
+    pub fn turn_right(self) -> Dir8 {
+        Dir8::ALL[(self as usize + 1) % 8]
+    }
+
+    /// An eighth of a turn anticlockwise.
+    pub fn turn_left(self) -> Dir8 {
+        Dir8::ALL[(self as usize + 7) % 8]
+    }
+
+    pub fn reverse(self) -> Dir8 {
+        Dir8::ALL[(self as usize + 4) % 8]
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self as usize % 2 == 1
+    }
+}
+
+impl From<Dir4> for Dir8 {
+    fn from(dir: Dir4) -> Dir8 {
+        Dir8::ALL[dir as usize * 2]
+    }
+}
+
+impl From<Dir4> for Point {
+    fn from(dir: Dir4) -> Point {
+        let (dx, dy) = dir.offset();
+        Point::new(dx, dy)
+    }
+}
+
+impl From<Dir8> for Point {
+    fn from(dir: Dir8) -> Point {
+        let (dx, dy) = dir.offset();
+        Point::new(dx, dy)
+    }
+}
+
+/// The direction of a unit step, or the vector back if it isn't one.
+impl TryFrom<Point> for Dir4 {
+    type Error = Point;
+
+    fn try_from(v: Point) -> Result<Dir4, Point> {
+        Dir4::ALL
+            .into_iter()
+            .find(|&dir| Point::from(dir) == v)
+            .ok_or(v)
+    }
+}
+
+/// The direction of a unit step, diagonals included, or the vector back if it isn't one.
+impl TryFrom<Point> for Dir8 {
+    type Error = Point;
+
+    fn try_from(v: Point) -> Result<Dir8, Point> {
+        Dir8::ALL
+            .into_iter()
+            .find(|&dir| Point::from(dir) == v)
+            .ok_or(v)
+    }
+}
+
+impl Add<Dir4> for Point {
+    type Output = Point;
+
+    fn add(self, dir: Dir4) -> Point {
+        self + Point::from(dir)
+    }
+}
+
+impl Sub<Dir4> for Point {
+    type Output = Point;
+
+    fn sub(self, dir: Dir4) -> Point {
+        self - Point::from(dir)
+    }
+}
+
+impl Add<Dir8> for Point {
+    type Output = Point;
+
+    fn add(self, dir: Dir8) -> Point {
+        self + Point::from(dir)
+    }
+}
+
+impl Sub<Dir8> for Point {
+    type Output = Point;
+
+    fn sub(self, dir: Dir8) -> Point {
+        self - Point::from(dir)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use enumset::EnumSet;
+
+    #[test]
+    fn turns() {
+        assert_eq!(Dir4::Up.turn_right(), Dir4::Right);
+        assert_eq!(Dir4::Up.turn_left(), Dir4::Left);
+        assert_eq!(Dir4::Left.reverse(), Dir4::Right);
+        assert_eq!(Dir4::Down.perpendicular(), [Dir4::Right, Dir4::Left]);
+        assert_eq!(Dir8::UpLeft.turn_right(), Dir8::Up);
+        assert_eq!(Dir8::Up.turn_left(), Dir8::UpLeft);
+        assert_eq!(Dir8::DownRight.reverse(), Dir8::UpLeft);
+        assert!(Dir8::DownLeft.is_diagonal());
+        assert_eq!(Dir8::from(Dir4::Left), Dir8::Left);
+    }
+    #[test]
+    fn vectors() {
+        let p = Point::new(3, 4);
+        assert_eq!(p + Dir4::Up, Point::new(3, 3));
+        assert_eq!(p - Dir4::Right, Point::new(2, 4));
+        assert_eq!(p + Dir8::DownLeft, Point::new(2, 5));
+        assert_eq!(Dir4::try_from(Point::new(0, 1)), Ok(Dir4::Down));
+        assert_eq!(Dir4::try_from(Point::new(1, 1)), Err(Point::new(1, 1)));
+        assert_eq!(Dir8::try_from(Point::new(1, 1)), Ok(Dir8::DownRight));
+    }
+    #[test]
+    fn arrows() {
+        for dir in Dir4::ALL {
+            assert_eq!(Dir4::from_arrow(dir.arrow()), Some(dir));
+        }
+        assert_eq!(Dir4::from_arrow('x'), None);
+    }
+    #[test]
+    fn sets() {
+        let mut seen = EnumSet::new();
+        seen |= Dir4::Up;
+        seen |= Dir4::Left;
+        assert!(seen.contains(Dir4::Up) && !seen.contains(Dir4::Down));
+        assert_eq!(seen.iter().collect::<Vec<_>>(), [Dir4::Up, Dir4::Left]);
+    }
+}
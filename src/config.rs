@@ -0,0 +1,288 @@
+use crate::args::{parse_day, parse_duration};
+use crate::days::AoCError;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::ErrorKind;
+use std::time::Duration;
+
+pub const CONFIG_FILE: &str = "aoc.toml";
+
+/// Settings from `aoc.toml`, each of which can be overridden by an `AOC_*` environment variable.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    year: Option<i32>,
+    input_dir: Option<String>,
+    answers_dir: Option<String>,
+    base_url: Option<String>,
+    session: Option<String>,
+    session_file: Option<String>,
+    user_agent: Option<String>,
+    request_interval: Option<String>,
+    timeout: Option<String>,
+    timeouts: Option<BTreeMap<String, String>>,
+    history_file: Option<String>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Config {
+    pub year: i32,
+    pub input_dir: String,
+    pub answers_dir: String,
+    pub base_url: String,
+    pub session: Option<String>,
+    pub session_file: String,
+    pub user_agent: String,
+    /// Minimum time between requests to the server, across runs
+    pub request_interval: Duration,
+    /// How long a part may run before it is reported as timed out; no limit if unset
+    pub timeout: Option<Duration>,
+    /// Per-day overrides of `timeout`
+    pub day_timeouts: BTreeMap<u32, Duration>,
+    /// Where `--report` keeps the results of past runs, to show trends
+    pub history_file: String,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            year: 2024,
+            input_dir: "input".to_string(),
+            answers_dir: "answers".to_string(),
+            base_url: "https://adventofcode.com".to_string(),
+            session: None,
+            session_file: ".cookie".to_string(),
+            user_agent: "github.com/jelaby/advent-of-code-2024-rs".to_string(),
+            request_interval: Duration::from_secs(5),
+            timeout: None,
+            day_timeouts: BTreeMap::new(),
+            history_file: "history.jsonl".to_string(),
+        }
+    }
+}
+
+impl Config {
+    /// Loads the config file (`AOC_CONFIG`, or `aoc.toml` if it exists) and applies the
+    /// environment on top of it.
+    pub fn load(path: Option<&str>) -> Result<Config, AoCError> {
+        let env = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+        let path = path.map(|p| p.to_string()).or_else(|| env("AOC_CONFIG"));
+
+        let content = match &path {
+            Some(path) => Some(fs::read_to_string(path)?),
+            None => match fs::read_to_string(CONFIG_FILE) {
+                Ok(content) => Some(content),
+                Err(e) if e.kind() == ErrorKind::NotFound => None,
+                Err(e) => return Err(AoCError::from(e)),
+            },
+        };
+
+        Config::from_sources(
+            path.as_deref().unwrap_or(CONFIG_FILE),
+            content.as_deref(),
+            env,
+        )
+    }
+
+    fn from_sources<F>(path: &str, content: Option<&str>, env: F) -> Result<Config, AoCError>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let file: ConfigFile = match content {
+            Some(content) => {
+                toml::from_str(content).map_err(|e| AoCError::ConfigFile(path.to_string(), e))?
+            }
+            None => ConfigFile::default(),
+        };
+        let defaults = Config::default();
+
+        let year = match env("AOC_YEAR") {
+            Some(year) => year
+                .parse()
+                .map_err(|_| AoCError::Config(format!("AOC_YEAR '{year}' is not a year")))?,
+            None => file.year.unwrap_or(defaults.year),
+        };
+
+        let request_interval = match env("AOC_REQUEST_INTERVAL").or(file.request_interval) {
+            Some(interval) => parse_duration(&interval)
+                .map_err(|e| AoCError::Config(format!("request_interval: {e}")))?,
+            None => defaults.request_interval,
+        };
+
+        let timeout = match env("AOC_TIMEOUT").or(file.timeout) {
+            Some(timeout) => Some(
+                parse_duration(&timeout).map_err(|e| AoCError::Config(format!("timeout: {e}")))?,
+            ),
+            None => defaults.timeout,
+        };
+
+        let mut day_timeouts = BTreeMap::new();
+        for (day, timeout) in file.timeouts.unwrap_or_default() {
+            let number =
+                parse_day(&day).map_err(|e| AoCError::Config(format!("timeouts.{day}: {e}")))?;
+            let timeout = parse_duration(&timeout)
+                .map_err(|e| AoCError::Config(format!("timeouts.{day}: {e}")))?;
+            day_timeouts.insert(number, timeout);
+        }
+
+        Ok(Config {
+            year,
+            input_dir: env("AOC_INPUT_DIR")
+                .or(file.input_dir)
+                .unwrap_or(defaults.input_dir),
+            answers_dir: env("AOC_ANSWERS_DIR")
+                .or(file.answers_dir)
+                .unwrap_or(defaults.answers_dir),
+            base_url: env("AOC_BASE_URL")
+                .or(file.base_url)
+                .unwrap_or(defaults.base_url)
+                .trim_end_matches('/')
+                .to_string(),
+            session: env("AOC_SESSION").or(file.session),
+            session_file: env("AOC_SESSION_FILE")
+                .or(file.session_file)
+                .unwrap_or(defaults.session_file),
+            user_agent: env("AOC_USER_AGENT")
+                .or(file.user_agent)
+                .unwrap_or(defaults.user_agent),
+            request_interval,
+            timeout,
+            day_timeouts,
+            history_file: env("AOC_HISTORY_FILE")
+                .or(file.history_file)
+                .unwrap_or(defaults.history_file),
+        })
+    }
+
+    /// The time limit for each part of `day`.
+    pub fn timeout_for(&self, day: u32) -> Option<Duration> {
+        self.day_timeouts.get(&day).copied().or(self.timeout)
+    }
+
+    /// The `Cookie` header value for the session, from the config or else the session file.
+    pub fn cookie(&self) -> Result<String, AoCError> {
+        let session = match &self.session {
+            Some(session) => session.clone(),
+            None => fs::read_to_string(&self.session_file).map_err(AoCError::CookieFile)?,
+        };
+        let session = session.trim();
+
+        if session.starts_with("session=") {
+            Ok(session.to_string())
+        } else {
+            Ok(format!("session={session}"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |name| vars.get(name).cloned()
+    }
+
+    #[test]
+    fn defaults() {
+        assert_eq!(
+            Config::from_sources(CONFIG_FILE, None, env(&[])).unwrap(),
+            Config::default()
+        );
+    }
+    #[test]
+    fn file() {
+        let config = Config::from_sources(
+            CONFIG_FILE,
+            Some("year = 2023\ninput_dir = \"inputs/2023\"\nbase_url = \"http://localhost:8080/\"\nrequest_interval = \"500ms\""),
+            env(&[]),
+        )
+        .unwrap();
+        assert_eq!(config.year, 2023);
+        assert_eq!(config.request_interval, Duration::from_millis(500));
+        assert_eq!(config.input_dir, "inputs/2023");
+        assert_eq!(config.base_url, "http://localhost:8080");
+        assert_eq!(config.answers_dir, "answers");
+    }
+    #[test]
+    fn env_overrides_file() {
+        let config = Config::from_sources(
+            CONFIG_FILE,
+            Some("year = 2023\ninput_dir = \"inputs/2023\"\nsession = \"from-file\""),
+            env(&[
+                ("AOC_YEAR", "2022"),
+                ("AOC_INPUT_DIR", "/tmp/in"),
+                ("AOC_SESSION", "from-env"),
+            ]),
+        )
+        .unwrap();
+        assert_eq!(config.year, 2022);
+        assert_eq!(config.input_dir, "/tmp/in");
+        assert_eq!(config.session, Some("from-env".to_string()));
+    }
+    #[test]
+    fn timeouts() {
+        let config = Config::from_sources(
+            CONFIG_FILE,
+            Some("timeout = \"30s\"\n[timeouts]\n14 = \"2m\"\n24 = \"500ms\""),
+            env(&[]),
+        )
+        .unwrap();
+        assert_eq!(config.timeout_for(1), Some(Duration::from_secs(30)));
+        assert_eq!(config.timeout_for(14), Some(Duration::from_secs(120)));
+        assert_eq!(config.timeout_for(24), Some(Duration::from_millis(500)));
+
+        let config =
+            Config::from_sources(CONFIG_FILE, None, env(&[("AOC_TIMEOUT", "10s")])).unwrap();
+        assert_eq!(config.timeout_for(14), Some(Duration::from_secs(10)));
+
+        assert_eq!(Config::default().timeout_for(14), None);
+        assert!(matches!(
+            Config::from_sources(CONFIG_FILE, Some("[timeouts]\n26 = \"1s\""), env(&[])),
+            Err(AoCError::Config(_))
+        ));
+    }
+    #[test]
+    fn invalid() {
+        assert!(matches!(
+            Config::from_sources(CONFIG_FILE, None, env(&[("AOC_YEAR", "next")])),
+            Err(AoCError::Config(_))
+        ));
+        assert!(matches!(
+            Config::from_sources(CONFIG_FILE, None, env(&[("AOC_REQUEST_INTERVAL", "soon")])),
+            Err(AoCError::Config(_))
+        ));
+        assert!(matches!(
+            Config::from_sources(CONFIG_FILE, Some("yaer = 2023"), env(&[])),
+            Err(AoCError::ConfigFile(_, _))
+        ));
+    }
+    #[test]
+    fn cookie() {
+        let config = Config {
+            session: Some("abc123\n".to_string()),
+            ..Config::default()
+        };
+        assert_eq!(config.cookie().unwrap(), "session=abc123");
+
+        let config = Config {
+            session: Some("session=abc123".to_string()),
+            ..Config::default()
+        };
+        assert_eq!(config.cookie().unwrap(), "session=abc123");
+    }
+    #[test]
+    fn cookie_missing_file() {
+        let config = Config {
+            session_file: "/nonexistent/.cookie".to_string(),
+            ..Config::default()
+        };
+        assert!(matches!(config.cookie(), Err(AoCError::CookieFile(_))));
+    }
+}
@@ -1,21 +1,31 @@
-use crate::days;
+use crate::days::{self, AoCError, Answer};
 
 pub struct Day;
 
+fn parse(input: &str) -> Result<(Vec<i64>, Vec<i64>), AoCError> {
+    let mut left = vec![];
+    let mut right = vec![];
+
+    for line in input.split_terminator("\n") {
+        let mut parts = line.split_whitespace();
+        for list in [&mut left, &mut right] {
+            let part = parts
+                .next()
+                .ok_or_else(|| AoCError::parse_at(input, &line[line.len()..], "expected two numbers"))?;
+            list.push(days::parse(input, part)?);
+        }
+    }
+
+    Ok((left, right))
+}
+
 impl days::Day for Day {
     fn day(&self) -> u32 {
         1
     }
 
-    fn part1(&self, input: &str) -> Option<String> {
-        let mut left = vec![];
-        let mut right = vec![];
-
-        for line in input.split_terminator("\n") {
-            let mut parts = line.split_whitespace();
-            left.push(parts.next().unwrap().parse::<i64>().unwrap());
-            right.push(parts.next().unwrap().parse::<i64>().unwrap());
-        }
+    fn part1(&self, input: &str) -> Result<Answer, AoCError> {
+        let (mut left, mut right) = parse(input)?;
 
         left.sort();
         right.sort();
@@ -25,17 +35,10 @@ impl days::Day for Day {
             result += (pair.0 - pair.1).abs();
         }
 
-        Some(result).map(|r| r.to_string())
+        Ok(result.into())
     }
-    fn part2(&self, input: &str) -> Option<String> {
-        let mut left = vec![];
-        let mut right = vec![];
-
-        for line in input.split("\n") {
-            let mut parts = line.split_whitespace();
-            left.push(parts.next().unwrap().parse::<i64>().unwrap());
-            right.push(parts.next().unwrap().parse::<i64>().unwrap());
-        }
+    fn part2(&self, input: &str) -> Result<Answer, AoCError> {
+        let (mut left, mut right) = parse(input)?;
 
         left.sort();
         right.sort();
@@ -48,33 +51,6 @@ impl days::Day for Day {
             result += l * (j - i) as i64;
         }
 
-        Some(result).map(|r| r.to_string())
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::days::Day;
-
-    const DAY: crate::day1::Day = crate::day1::Day;
-    #[test]
-    fn part1_example1() {
-        let text = "3   4
-4   3
-2   5
-1   3
-3   9
-3   3";
-        assert_eq!(DAY.part1(text), Some("11".to_string()))
-    }
-    #[test]
-    fn part2_example1() {
-        let text = "3   4
-4   3
-2   5
-1   3
-3   9
-3   3";
-        assert_eq!(DAY.part2(text), Some("31".to_string()))
+        Ok(result.into())
     }
 }
@@ -1,16 +1,16 @@
 use std::cmp::max;
-use std::collections::HashMap;
-use crate::days;
+use crate::days::{self, AoCError, Answer, Param, Params};
+use crate::memo::Memo;
 
 pub struct Day;
 
 impl Day {}
 
-fn parse(input: &str) -> Vec<i64> {
+fn parse(input: &str) -> Result<Vec<i64>, AoCError> {
     input
         .split_terminator('\n')
         .flat_map(|line| line.split_whitespace())
-        .map(|word| word.parse::<i64>().unwrap())
+        .map(|word| days::parse(input, word))
         .collect()
 }
 
@@ -24,82 +24,71 @@ fn get_length(i: i64) -> usize {
     return max(result, 1);
 }
 
-fn count_splits(stone: i64, iterations: usize) -> usize {
-    count_splits_cached(stone, iterations, &mut HashMap::new())
-}
-fn count_splits_cached(stone: i64, iterations: usize, cache: &mut HashMap<(i64,usize),usize>) -> usize {
+fn count_splits(stone: i64, iterations: usize, memo: &Memo<(i64, usize), usize>) -> usize {
     if iterations == 0 {
         1
     } else {
-        match cache.get(&(stone, iterations)) {
-            Some(count) => *count,
-            None => {
-                let count = {
-                    if stone == 0 {
-                        count_splits_cached(1, iterations - 1, cache)
-                    } else {
-                        let length = get_length(stone);
-                        if length % 2 == 0 {
-                            let divisor = num::pow(10, length / 2);
-                            count_splits_cached(stone / divisor, iterations - 1, cache)
-                                + count_splits_cached(stone % divisor, iterations - 1, cache)
-                        } else {
-                            count_splits_cached(stone * 2024, iterations - 1, cache)
-                        }
-                    }
-                };
-                cache.insert((stone, iterations), count);
-                count
+        memo.get_or_insert_with((stone, iterations), || {
+            if stone == 0 {
+                count_splits(1, iterations - 1, memo)
+            } else {
+                let length = get_length(stone);
+                if length % 2 == 0 {
+                    let divisor = num::pow(10, length / 2);
+                    count_splits(stone / divisor, iterations - 1, memo)
+                        + count_splits(stone % divisor, iterations - 1, memo)
+                } else {
+                    count_splits(stone * 2024, iterations - 1, memo)
+                }
             }
-        }
+        })
     }
 }
 
 fn iterate_n(stones: &Vec<i64>, count: usize) -> usize {
+    let memo = Memo::new();
     stones.iter()
-        .map(|&stone| count_splits(stone, count))
+        .map(|&stone| count_splits(stone, count, &memo))
         .sum()
 }
 
+const BLINKS: Param = Param::per_part("blinks", "25", "75", "times the stones change");
+
 impl days::Day for Day {
     fn day(&self) -> u32 {
         11
     }
 
-    fn part1(&self, input: &str) -> Option<String> {
-        let stones = parse(input);
-        Some(iterate_n(&stones, 25) as i64).map(|r| r.to_string())
+    fn part1(&self, input: &str) -> Result<Answer, AoCError> {
+        self.part1_with(input, &Params::default())
+    }
+    fn part2(&self, input: &str) -> Result<Answer, AoCError> {
+        self.part2_with(input, &Params::default())
+    }
+    fn params(&self) -> &'static [Param] {
+        &[BLINKS]
+    }
+    fn part1_with(&self, input: &str, params: &Params) -> Result<Answer, AoCError> {
+        let stones = parse(input)?;
+        Ok(iterate_n(&stones, params.get(&BLINKS, 1)?).into())
     }
-    fn part2(&self, input: &str) -> Option<String> {
-        let stones = parse(input);
-        Some(iterate_n(&stones, 75) as i64).map(|r| r.to_string())
+    fn part2_with(&self, input: &str, params: &Params) -> Result<Answer, AoCError> {
+        let stones = parse(input)?;
+        Ok(iterate_n(&stones, params.get(&BLINKS, 2)?).into())
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::days::Day;
+    use super::*;
 
-    const DAY: super::Day = super::Day;
-    #[test]
-    fn part1_example1() {
-        let text = "125 17";
-        assert_eq!(DAY.part1(text), Some("55312".to_string()))
-    }
-    #[test]
-    fn part1_example1_breakdown2() {
-        let stones = super::parse("125 17");
-        assert_eq!(super::iterate_n(&stones, 0), 2);
-        assert_eq!(super::iterate_n(&stones, 1), 3);
-        assert_eq!(super::iterate_n(&stones, 2), 4);
-        assert_eq!(super::iterate_n(&stones, 3), 5);
-        assert_eq!(super::iterate_n(&stones, 4), 9);
-        assert_eq!(super::iterate_n(&stones, 5), 13);
-        assert_eq!(super::iterate_n(&stones, 6), 22);
-    }
     #[test]
-    fn part2_example1() {
-        let text = "125 17";
-        assert_eq!(DAY.part2(text), Some("65601038650482".to_string()))
+    fn stones_share_a_memo() {
+        let memo = Memo::new();
+        assert_eq!(count_splits(125, 6, &memo), 7);
+        let misses = memo.stats().misses;
+        // a second stone with the same engraving is only looked up
+        assert_eq!(count_splits(125, 6, &memo), 7);
+        assert_eq!(memo.stats().misses, misses);
     }
 }
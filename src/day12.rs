@@ -1,37 +1,33 @@
-use std::collections::HashSet;
-use itertools::Itertools;
-use crate::days;
+use enumset::EnumSet;
+use crate::compass::Dir4;
+use crate::days::{self, AoCError, Answer};
+use crate::grid::Grid;
 
 pub struct Day;
 
 impl Day {}
 
-fn parse(input: &str) -> Vec<Vec<char>> {
-    input.lines().map(|line| line.chars().collect()).collect()
+fn parse(input: &str) -> Result<Grid<char>, AoCError> {
+    Grid::chars(input)
 }
 
-const DIR: [(i64, i64); 4] = [(0, 1), (1, 0), (0, -1), (-1, 0)];
-fn count(map: &Vec<Vec<char>>, x: usize, y: usize, visited: &mut Vec<Vec<bool>>) -> (usize, usize) {
-    if visited[y][x] {
+fn count(map: &Grid<char>, x: usize, y: usize, visited: &mut Grid<bool>) -> (usize, usize) {
+    if visited[(x, y)] {
         return (0, 0);
     }
 
-    visited[y][x] = true;
+    visited[(x, y)] = true;
 
     let mut area = 1;
     let mut boundary = 0;
-    let t = map[y][x];
+    let t = map[(x, y)];
 
-    for (dx, dy) in DIR {
+    for dir in Dir4::ALL {
+        let (dx, dy) = dir.offset();
         let neighbour_x = x as i64 + dx;
         let neighbour_y = y as i64 + dy;
 
-        if neighbour_x < 0
-            || neighbour_x >= map[y].len() as i64
-            || neighbour_y < 0
-            || neighbour_y >= map.len() as i64
-            || map[neighbour_y as usize][neighbour_x as usize] != t
-        {
+        if !is_same(map, t, neighbour_x, neighbour_y) {
             boundary += 1;
         } else {
             let (neighbour_area, neighbour_boundary) =
@@ -45,66 +41,61 @@ fn count(map: &Vec<Vec<char>>, x: usize, y: usize, visited: &mut Vec<Vec<bool>>)
 }
 
 
-fn count_all(map: &Vec<Vec<char>>) -> usize{
-    let mut visited = vec![vec![false; map[0].len()]; map.len()];
+fn count_all(map: &Grid<char>) -> usize{
+    let mut visited = Grid::new(map.width(), map.height(), false);
     let mut score = 0;
 
-    for y in 0..map.len() {
-        for x in 0..map[y].len() {
-            let (next_area, next_boundary) = crate::day12::count(&map, x, y, &mut visited);
-            score += next_area * next_boundary;
-        }
+    for (x, y) in map.positions() {
+        let (next_area, next_boundary) = crate::day12::count(&map, x, y, &mut visited);
+        score += next_area * next_boundary;
     }
 
     score
 }
 
-fn calculate_area(map: &Vec<Vec<char>>, x: usize, y: usize, visited: &mut Vec<Vec<bool>>) -> usize {
-    if visited[y][x] {
+fn calculate_area(map: &Grid<char>, x: usize, y: usize, visited: &mut Grid<bool>) -> usize {
+    if visited[(x, y)] {
         return 0;
     }
-    visited[y][x] = true;
+    visited[(x, y)] = true;
     let mut area = 1;
-    let t = map[y][x];
+    let t = map[(x, y)];
 
-    for (dx, dy) in DIR {
+    for dir in Dir4::ALL {
+        let (dx, dy) = dir.offset();
         let neighbour_x = x as i64 + dx;
         let neighbour_y = y as i64 + dy;
 
-        if neighbour_x < 0
-            || neighbour_x >= map[y].len() as i64
-            || neighbour_y < 0
-            || neighbour_y >= map.len() as i64
-            || map[neighbour_y as usize][neighbour_x as usize] != t
-        {} else {
+        if is_same(map, t, neighbour_x, neighbour_y) {
             area += calculate_area(map, neighbour_x as usize, neighbour_y as usize, visited);
         }
     }
     area
 }
 
-fn is_same(map: &Vec<Vec<char>>, t: char, x:i64, y:i64) -> bool {
-    x >= 0 && x < map[0].len() as i64 && y >= 0 && y < map.len() as i64 && map[y as usize][x as usize] == t
+fn is_same(map: &Grid<char>, t: char, x:i64, y:i64) -> bool {
+    map.get(x, y) == Some(&t)
 }
 
-fn count_sides(map: &Vec<Vec<char>>, x: usize, y: usize, visited: &mut Vec<Vec<HashSet<(i64,i64)>>>) -> usize {
+fn count_sides(map: &Grid<char>, x: usize, y: usize, visited: &mut Grid<EnumSet<Dir4>>) -> usize {
 
     let mut result = 0;
-    let t = map[y][x];
+    let t = map[(x, y)];
 
-    for dir in &DIR {
-        if !visited[y][x].iter().contains(&dir) {
-            visited[y][x].insert(dir.clone());
+    for dir in Dir4::ALL {
+        if !visited[(x, y)].contains(dir) {
+            visited[(x, y)] |= dir;
 
-            let (dx,dy) = *dir;
+            let (dx,dy) = dir.offset();
 
             let neighbour_x = x as i64 + dx;
             let neighbour_y = y as i64 + dy;
 
             if !is_same(map, t, neighbour_x, neighbour_y) {
                 result += 1;
-                trace_edge(&map, x, y, dy,dx, dx, dy, visited);
-                trace_edge(&map, x, y, -dy,-dx, dx, dy, visited);
+                for along in dir.perpendicular() {
+                    trace_edge(&map, x, y, along, dir, visited);
+                }
             } else {
                 result += count_sides(map, neighbour_x as usize, neighbour_y as usize, visited);
             }
@@ -114,14 +105,15 @@ fn count_sides(map: &Vec<Vec<char>>, x: usize, y: usize, visited: &mut Vec<Vec<H
     result
 }
 
-fn trace_edge(map: &Vec<Vec<char>>, x: usize, y: usize, dx: i64, dy: i64, wallx:i64, wally:i64, visited: &mut Vec<Vec<HashSet<(i64,i64)>>>) {
+fn trace_edge(map: &Grid<char>, x: usize, y: usize, along: Dir4, wall: Dir4, visited: &mut Grid<EnumSet<Dir4>>) {
 
-    visited[y][x].insert((wallx,wally));
+    visited[(x, y)] |= wall;
 
+    let (dx, dy) = along.offset();
     let next_x = x as i64 + dx;
     let next_y = y as i64 + dy;
 
-    let t = map[y][x];
+    let t = map[(x, y)];
 
     if !is_same(&map, t, next_x, next_y) {
         // reached an inside corner -- stop tracing
@@ -130,6 +122,7 @@ fn trace_edge(map: &Vec<Vec<char>>, x: usize, y: usize, dx: i64, dy: i64, wallx:
     }
 
     // is the wall still there?
+    let (wallx, wally) = wall.offset();
     let over_wall_x = next_x + wallx;
     let over_wall_y = next_y + wally;
 
@@ -139,21 +132,19 @@ fn trace_edge(map: &Vec<Vec<char>>, x: usize, y: usize, dx: i64, dy: i64, wallx:
     }
 
     // still on a wall, so trace along it further
-    trace_edge(&map, next_x as usize, next_y as usize, dx, dy, wallx, wally, visited);
+    trace_edge(&map, next_x as usize, next_y as usize, along, wall, visited);
 }
 
-fn count_all_2(map: &Vec<Vec<char>>) -> usize{
-    let mut visited = vec![vec![false; map[0].len()]; map.len()];
-    let mut visited2 = vec![vec![HashSet::new(); map[0].len()]; map.len()];
+fn count_all_2(map: &Grid<char>) -> usize{
+    let mut visited = Grid::new(map.width(), map.height(), false);
+    let mut visited2 = Grid::new(map.width(), map.height(), EnumSet::new());
     let mut score = 0;
 
-    for y in 0..map.len() {
-        for x in 0..map[y].len() {
-            let next_area = calculate_area(map,x,y,&mut visited);
-            if next_area > 0 {
-                let sides = count_sides(&map, x, y, &mut visited2);
-                score += next_area * sides;
-            }
+    for (x, y) in map.positions() {
+        let next_area = calculate_area(map,x,y,&mut visited);
+        if next_area > 0 {
+            let sides = count_sides(&map, x, y, &mut visited2);
+            score += next_area * sides;
         }
     }
 
@@ -165,114 +156,13 @@ impl days::Day for Day {
         12
     }
 
-    fn part1(&self, input: &str) -> Option<String> {
-        let map = parse(input);
-
-        Some(count_all(&map) as i64).map(|r| r.to_string())
-    }
-    fn part2(&self, input: &str) -> Option<String> {
-        let map = parse(input);
-        Some(count_all_2(&map) as i64).map(|r| r.to_string())
-    }
-}
+    fn part1(&self, input: &str) -> Result<Answer, AoCError> {
+        let map = parse(input)?;
 
-#[cfg(test)]
-mod tests {
-    use crate::days::Day;
-
-    const DAY: super::Day = super::Day;
-    #[test]
-    fn part1_example1() {
-        let text = "\
-AAAA
-BBCD
-BBCC
-EEEC";
-        assert_eq!(DAY.part1(text), Some("140".to_string()))
-    }
-    #[test]
-    fn part1_example2() {
-        let text = "\
-OOOOO
-OXOXO
-OOOOO
-OXOXO
-OOOOO";
-        assert_eq!(DAY.part1(text), Some("772".to_string()))
-    }
-    #[test]
-    fn part1_small() {
-        let text = "O";
-        assert_eq!(DAY.part1(text), Some("4".to_string()))
-    }
-    #[test]
-    fn part1_small2() {
-        let text = "OO";
-        assert_eq!(DAY.part1(text), Some("12".to_string()))
-    }
-    #[test]
-    fn part1_two() {
-        let text = "AB";
-        assert_eq!(DAY.part1(text), Some("8".to_string()))
-    }
-    #[test]
-    fn part2_example1() {
-        let text = "\
-AAAA
-BBCD
-BBCC
-EEEC";
-        assert_eq!(DAY.part2(text), Some("80".to_string()))
-    }
-    #[test]
-    fn part2_example2() {
-        let text = "\
-EEEEE
-EXXXX
-EEEEE
-EXXXX
-EEEEE";
-        assert_eq!(DAY.part2(text), Some("236".to_string()))
-    }
-    #[test]
-    fn part2_example3() {
-        let text = "\
-AAAAAA
-AAABBA
-AAABBA
-ABBAAA
-ABBAAA
-AAAAAA";
-        assert_eq!(DAY.part2(text), Some("368".to_string()))
-    }
-    #[test]
-    fn part2_example4() {
-        let text = "\
-RRRRIICCFF
-RRRRIICCCF
-VVRRRCCFFF
-VVRCCCJFFF
-VVVVCJJCFE
-VVIVCCJJEE
-VVIIICJJEE
-MIIIIIJJEE
-MIIISIJEEE
-MMMISSJEEE";
-        assert_eq!(DAY.part2(text), Some("1206".to_string()))
-    }
-    #[test]
-    fn part2_small() {
-        let text = "O";
-        assert_eq!(DAY.part2(text), Some("4".to_string()))
-    }
-    #[test]
-    fn part2_small2() {
-        let text = "OO";
-        assert_eq!(DAY.part2(text), Some("8".to_string()))
+        Ok(count_all(&map).into())
     }
-    #[test]
-    fn part2_two() {
-        let text = "AB";
-        assert_eq!(DAY.part2(text), Some("8".to_string()))
+    fn part2(&self, input: &str) -> Result<Answer, AoCError> {
+        let map = parse(input)?;
+        Ok(count_all_2(&map).into())
     }
 }
@@ -1,7 +1,5 @@
-use crate::days;
-use itertools::Itertools;
-use regex::Regex;
-use std::sync::LazyLock;
+use crate::days::{self, AoCError, Answer};
+use crate::parsing;
 use nalgebra::Vector2;
 
 pub struct Day;
@@ -14,23 +12,28 @@ struct Machine {
     prize: Vector2<i64>,
 }
 
-fn parse(input: &str) -> Vec<Machine> {
-    static PATTERN: LazyLock<Regex> =
-        LazyLock::new(|| Regex::new(r"\w+: X[=+](?<x>\d+), Y[=+](?<y>\d+)").unwrap());
-
-    fn to_vector(line: &str) -> Vector2<i64> {
-        let caps = PATTERN.captures(line).unwrap();
-        Vector2::new(caps["x"].parse().unwrap(), caps["y"].parse().unwrap())
-    }
-
-    input
-        .lines()
-        .chunks(4)
-        .into_iter()
-        .map(|mut chunk| Machine {
-            a: to_vector(chunk.next().unwrap()),
-            b: to_vector(chunk.next().unwrap()),
-            prize: to_vector(chunk.next().unwrap()),
+fn parse(input: &str) -> Result<Vec<Machine>, AoCError> {
+    let to_vector = |line: &str, template: &str| -> Result<Vector2<i64>, AoCError> {
+        let [x, y] = parsing::fields(input, line, template)?;
+        Ok(Vector2::new(days::parse(input, x)?, days::parse(input, y)?))
+    };
+
+    parsing::sections(input)
+        .map(|section| {
+            let mut lines = section.lines();
+            let machine = Machine {
+                a: to_vector(parsing::next_line(section, &mut lines), "Button A: X+{x}, Y+{y}")?,
+                b: to_vector(parsing::next_line(section, &mut lines), "Button B: X+{x}, Y+{y}")?,
+                prize: to_vector(parsing::next_line(section, &mut lines), "Prize: X={x}, Y={y}")?,
+            };
+            match lines.next() {
+                Some(extra) => Err(AoCError::parse_at(
+                    input,
+                    extra,
+                    "expected a blank line after the prize",
+                )),
+                None => Ok(machine),
+            }
         })
         .collect()
 }
@@ -95,21 +98,22 @@ impl days::Day for Day {
         13
     }
 
-    fn part1(&self, input: &str) -> Option<String> {
-        let machines = parse(input);
+    fn part1(&self, input: &str) -> Result<Answer, AoCError> {
+        let machines = parse(input)?;
 
-        Some(
+        Ok(
             machines
                 .iter()
                 .filter_map(|m| find_moves(m))
                 .map(|(a, b)| a * 3 + b)
-                .sum::<i64>(),
-        ).map(|r| r.to_string())
+                .sum::<i64>()
+                .into(),
+        )
     }
-    fn part2(&self, input: &str) -> Option<String> {
-        let machines = parse(input);
+    fn part2(&self, input: &str) -> Result<Answer, AoCError> {
+        let machines = parse(input)?;
 
-        Some(
+        Ok(
             machines
                 .iter()
                 .map(|m| Machine {
@@ -119,54 +123,35 @@ impl days::Day for Day {
                 })
                 .filter_map(|m| find_moves(&m))
                 .map(|(a, b)| a * 3 + b)
-                .sum::<i64>(),
-        ).map(|r| r.to_string())
+                .sum::<i64>()
+                .into(),
+        )
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::days::Day;
+    use super::*;
 
-    const DAY: super::Day = super::Day;
-    #[test]
-    fn part1_example1() {
-        let text = "\
-Button A: X+94, Y+34
-Button B: X+22, Y+67
-Prize: X=8400, Y=5400
-
-Button A: X+26, Y+66
-Button B: X+67, Y+21
-Prize: X=12748, Y=12176
-
-Button A: X+17, Y+86
-Button B: X+84, Y+37
-Prize: X=7870, Y=6450
-
-Button A: X+69, Y+23
-Button B: X+27, Y+71
-Prize: X=18641, Y=10279";
-        assert_eq!(DAY.part1(text), Some("480".to_string()))
+    fn error(input: &str) -> String {
+        parse(input).err().unwrap().to_string()
     }
+
     #[test]
-    fn part2_example1() {
-        let text = "\
-Button A: X+94, Y+34
-Button B: X+22, Y+67
-Prize: X=8400, Y=5400
-
-Button A: X+26, Y+66
-Button B: X+67, Y+21
-Prize: X=12748, Y=12176
-
-Button A: X+17, Y+86
-Button B: X+84, Y+37
-Prize: X=7870, Y=6450
-
-Button A: X+69, Y+23
-Button B: X+27, Y+71
-Prize: X=18641, Y=10279";
-        assert_eq!(DAY.part2(text), Some("875318608908".to_string()))
+    fn parse_errors() {
+        assert_eq!(
+            error(
+                "Button A: X+94, Y+34\nButton B: X+22, Y+67\nPrize: X=8400, Y=5400\nPrize: X=1, Y=2"
+            ),
+            "line 4, column 1: expected a blank line after the prize"
+        );
+        assert_eq!(
+            error("Button A: X+94, Y+34\nPrize: X=8400, Y=5400"),
+            "line 2, column 1: expected Button B: X+<x>, Y+<y>"
+        );
+        assert_eq!(
+            error("Button A: X+94, Y+34\nButton B: X+22, Y+67\nPrize: X=8400, Z=5400"),
+            "line 3, column 1: expected Prize: X=<x>, Y=<y>"
+        );
     }
 }
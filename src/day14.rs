@@ -1,7 +1,7 @@
-use crate::days;
+use crate::cancel;
+use crate::days::{self, AoCError, Answer, Param, Params};
+use crate::parsing;
 use nalgebra::{DMatrix, Dyn, OMatrix, Vector2};
-use regex::Regex;
-use std::sync::LazyLock;
 
 pub struct Day;
 
@@ -13,25 +13,18 @@ struct Robot {
     v: Vector2<i64>,
 }
 
-static LINE_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
-    Regex::new(r"p=(?<px>[0-9-]+),(?<py>[0-9-]+) v=(?<vx>[0-9-]+),(?<vy>[0-9-]+)").unwrap()
-});
-fn parse_line(line: &str) -> Robot {
-    let captures = LINE_PATTERN.captures(line).unwrap();
-    Robot {
-        p: Vector2::new(
-            captures["px"].parse().unwrap(),
-            captures["py"].parse().unwrap(),
-        ),
-        v: Vector2::new(
-            captures["vx"].parse().unwrap(),
-            captures["vy"].parse().unwrap(),
-        ),
-    }
+fn parse_line(input: &str, line: &str) -> Result<Robot, AoCError> {
+    let [px, py, vx, vy] = parsing::fields(input, line, "p={x},{y} v={x},{y}")?;
+    let number = |field| days::parse(input, field);
+
+    Ok(Robot {
+        p: Vector2::new(number(px)?, number(py)?),
+        v: Vector2::new(number(vx)?, number(vy)?),
+    })
 }
 
-fn parse(input: &str) -> Vec<Robot> {
-    input.lines().map(|line| parse_line(line)).collect()
+fn parse(input: &str) -> Result<Vec<Robot>, AoCError> {
+    input.lines().map(|line| parse_line(input, line)).collect()
 }
 
 fn go(robots: &Vec<Robot>, size: Vector2<i64>, time: i64) -> Vec<Robot> {
@@ -71,9 +64,20 @@ fn safety_factor(robots: &Vec<Robot>, size: Vector2<i64>) -> i64 {
     quadrants.iter().product()
 }
 
-fn do_part1(input: &str, sizex: i64, sizey: i64) -> Option<i64> {
-    let size = Vector2::new(sizex, sizey);
-    Some(safety_factor(&go(&parse(input), size, 100), size))
+fn room_size(sizex: i64, sizey: i64) -> Result<Vector2<i64>, AoCError> {
+    if sizex > 0 && sizey > 0 {
+        Ok(Vector2::new(sizex, sizey))
+    } else {
+        Err(AoCError::InvalidParameter(
+            "size".to_string(),
+            format!("{sizex}x{sizey} is not a room"),
+        ))
+    }
+}
+
+fn do_part1(input: &str, sizex: i64, sizey: i64) -> Result<i64, AoCError> {
+    let size = room_size(sizex, sizey)?;
+    Ok(safety_factor(&go(&parse(input)?, size, 100), size))
 }
 
 fn plot_robots(robots: &Vec<Robot>, size: &Vector2<i64>) -> OMatrix<usize, Dyn, Dyn> {
@@ -112,28 +116,41 @@ fn all_adjacent(robots: &Vec<Robot>, size: &Vector2<i64>, time: i64) -> bool {
     have_neighbours > robots.len() / 2
 }
 
-fn do_part2(input: &str, sizex: i64, sizey: i64) -> Option<i64> {
-    let size = Vector2::new(sizex, sizey);
-    let robots = parse(input);
+fn do_part2(input: &str, sizex: i64, sizey: i64) -> Result<i64, AoCError> {
+    let size = room_size(sizex, sizey)?;
+    let robots = parse(input)?;
 
     for time in 0..10000 {
+        cancel::check()?;
         if all_adjacent(&robots, &size, time) {
-            return Some(time);
+            return Ok(time);
         }
     }
-    None
+    Err(AoCError::unsolvable("no Christmas tree within 10000 seconds"))
 }
 
+const WIDTH: Param = Param::new("width", "101", "width of the room");
+const HEIGHT: Param = Param::new("height", "103", "height of the room");
+
 impl days::Day for Day {
     fn day(&self) -> u32 {
         14
     }
 
-    fn part1(&self, input: &str) -> Option<String> {
-        do_part1(input, 101, 103).map(|r| r.to_string())
+    fn part1(&self, input: &str) -> Result<Answer, AoCError> {
+        self.part1_with(input, &Params::default())
+    }
+    fn part2(&self, input: &str) -> Result<Answer, AoCError> {
+        self.part2_with(input, &Params::default())
+    }
+    fn params(&self) -> &'static [Param] {
+        &[WIDTH, HEIGHT]
+    }
+    fn part1_with(&self, input: &str, params: &Params) -> Result<Answer, AoCError> {
+        do_part1(input, params.get(&WIDTH, 1)?, params.get(&HEIGHT, 1)?).map(Answer::from)
     }
-    fn part2(&self, input: &str) -> Option<String> {
-        do_part2(input, 101, 103).map(|r| r.to_string())
+    fn part2_with(&self, input: &str, params: &Params) -> Result<Answer, AoCError> {
+        do_part2(input, params.get(&WIDTH, 2)?, params.get(&HEIGHT, 2)?).map(Answer::from)
     }
 }
 
@@ -144,21 +161,11 @@ mod tests {
     use nalgebra::Vector2;
 
     #[test]
-    fn part1_example1() {
-        let text = "\
-p=0,4 v=3,-3
-p=6,3 v=-1,-3
-p=10,3 v=-1,2
-p=2,0 v=2,-1
-p=0,0 v=1,3
-p=3,0 v=-2,-2
-p=7,6 v=-1,-3
-p=3,0 v=-1,-2
-p=9,3 v=2,3
-p=7,3 v=-1,2
-p=2,4 v=2,-3
-p=9,5 v=-3,-3";
-        assert_eq!(do_part1(text, 11, 7), Some(12))
+    fn part1_empty_room() {
+        assert!(matches!(
+            do_part1("p=0,4 v=3,-3", 0, 7),
+            Err(crate::days::AoCError::InvalidParameter(_, _))
+        ));
     }
 
     #[test]
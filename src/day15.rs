@@ -1,6 +1,7 @@
 use crate::day15::Block::*;
-use crate::day15::Dir::*;
-use crate::days;
+use crate::compass::{Dir4, Point};
+use crate::days::{self, AoCError, Answer};
+use crate::grid::Grid;
 use nalgebra::Vector2;
 
 pub struct Day;
@@ -17,94 +18,62 @@ enum Block {
     CrateRight,
 }
 
-#[derive(Copy, Clone, Debug)]
-enum Dir {
-    Left,
-    Right,
-    Up,
-    Down,
-}
+type Map = Grid<Block>;
 
-fn parse(input: &str, part2: bool) -> (Vec<Vec<Block>>, Vector2<usize>, Vec<Dir>) {
-    let mut parts = input.split_terminator("\n\n");
+fn parse(input: &str, part2: bool) -> Result<(Map, Vector2<usize>, Vec<Dir4>), AoCError> {
+    let (map, commands) = input.split_once("\n\n").ok_or_else(|| {
+        AoCError::parse_at(input, &input[input.len()..], "expected a map, a blank line, then moves")
+    })?;
 
-    let map: Vec<Vec<Block>> = parts
-        .next()
-        .unwrap()
-        .lines()
-        .map(|line| {
-            line.chars()
-                .map(|c| match c {
-                    '#' => Wall,
-                    '.' => Space,
-                    'O' => Crate,
-                    '@' => Robot,
-                    _ => panic!("Unrecognised map char {c}"),
-                })
-                .collect()
-        })
-        .collect();
+    let map = Grid::parse(map, |c| match c {
+        '#' => Ok(Wall),
+        '.' => Ok(Space),
+        'O' => Ok(Crate),
+        '@' => Ok(Robot),
+        _ => Err(format!("unrecognised map char {c}")),
+    })?;
 
     let map = if part2 { to_part2(&map) } else { map };
 
-    let robot = map
-        .iter()
-        .enumerate()
-        .find_map(|(y, row)| {
-            row.iter()
-                .enumerate()
-                .find(|(_, &block)| matches!(block, Robot))
-                .map(|(x, _)| Vector2::new(x, y))
-        })
-        .unwrap();
+    let (x, y) = map
+        .find(|&block| matches!(block, Robot))
+        .ok_or_else(|| AoCError::parse_at(input, input, "no robot (@) on the map"))?;
+    let robot = Vector2::new(x, y);
 
-    let commands = parts
-        .next()
-        .unwrap()
+    let commands = commands
         .lines()
-        .flat_map(|line| line.chars())
-        .map(|c| match c {
-            '<' => Left,
-            '>' => Right,
-            '^' => Up,
-            'v' => Down,
-            _ => panic!("Unrecognised command char {c}"),
+        .flat_map(|line| line.char_indices().map(move |(i, c)| (&line[i..], c)))
+        .map(|(at, c)| {
+            Dir4::from_arrow(c).ok_or_else(|| {
+                AoCError::parse_at(input, at, format!("unrecognised command char {c}"))
+            })
         })
-        .collect();
-
-    (map, robot, commands)
-}
+        .collect::<Result<_, _>>()?;
 
-fn to_part2(map: &Vec<Vec<Block>>) -> Vec<Vec<Block>> {
-    map.iter()
-        .map(|row| {
-            row.iter()
-                .flat_map(|block| match block {
-                    Space => [Space, Space].into_iter(),
-                    Robot => [Robot, Space].into_iter(),
-                    Wall => [Wall, Wall].into_iter(),
-                    Crate => [CrateLeft, CrateRight].into_iter(),
-                    _ => panic!("Unexpected {block:?} while converting to part 2"),
-                })
-                .collect()
-        })
-        .collect()
+    Ok((map, robot, commands))
 }
 
-fn vec_for(dir: Dir) -> Vector2<i64> {
-    match dir {
-        Left => Vector2::new(-1, 0),
-        Right => Vector2::new(1, 0),
-        Up => Vector2::new(0, -1),
-        Down => Vector2::new(0, 1),
+fn to_part2(map: &Map) -> Map {
+    let mut result = Grid::new(map.width() * 2, map.height(), Space);
+    for ((x, y), block) in map.iter() {
+        let [left, right] = match block {
+            Space => [Space, Space],
+            Robot => [Robot, Space],
+            Wall => [Wall, Wall],
+            Crate => [CrateLeft, CrateRight],
+            _ => panic!("Unexpected {block:?} while converting to part 2"),
+        };
+        result[(x * 2, y)] = left;
+        result[(x * 2 + 1, y)] = right;
     }
+    result
 }
 
-fn can_move_block(map: &mut Vec<Vec<Block>>, p: Vector2<usize>, v: Vector2<i64>) -> bool {
+fn can_move_block(map: &mut Map, p: Vector2<usize>, v: Point) -> bool {
     let n = (p.cast() + v).map(|i| i as usize);
     let r = Vector2::new(1, 0);
 
-    match map[p.y][p.x] {
+    match map[(p.x, p.y)] {
         Space => true,
         Wall => false,
         Crate => can_move_block(map, n, v),
@@ -124,7 +93,7 @@ fn can_move_block(map: &mut Vec<Vec<Block>>, p: Vector2<usize>, v: Vector2<i64>)
     }
 }
 
-fn move_block(map: &mut Vec<Vec<Block>>, p: Vector2<usize>, v: Vector2<i64>) -> bool {
+fn move_block(map: &mut Map, p: Vector2<usize>, v: Point) -> bool {
     let n = (p.cast() + v).map(|i| i as usize);
     let r = Vector2::new(1, 0);
 
@@ -132,33 +101,33 @@ fn move_block(map: &mut Vec<Vec<Block>>, p: Vector2<usize>, v: Vector2<i64>) ->
         return false;
     }
 
-    match map[p.y][p.x] {
+    match map[(p.x, p.y)] {
         Space => {
         }
         Wall => panic!("Tried to move wall at {p:?}+{v:?} => {n:?}"),
         Crate => {
             move_block(map, n, v);
-            map[n.y][n.x] = map[p.y][p.x];
-            map[p.y][p.x] = Space;
+            map[(n.x, n.y)] = map[(p.x, p.y)];
+            map[(p.x, p.y)] = Space;
         }
         CrateLeft => {
             if v.x == 1 {
                 move_block(map, n + r, v);
-                map[n.y][n.x + 1] = map[n.y][n.x];
-                map[n.y][n.x] = map[p.y][p.x];
-                map[p.y][p.x] = Space;
+                map[(n.x + 1, n.y)] = map[(n.x, n.y)];
+                map[(n.x, n.y)] = map[(p.x, p.y)];
+                map[(p.x, p.y)] = Space;
             } else if v.x == -1 {
                 move_block(map, n, v);
-                map[n.y][n.x] = map[p.y][p.x];
-                map[p.y][p.x] = map[p.y][p.x + 1];
-                map[p.y][p.x + 1] = Space;
+                map[(n.x, n.y)] = map[(p.x, p.y)];
+                map[(p.x, p.y)] = map[(p.x + 1, p.y)];
+                map[(p.x + 1, p.y)] = Space;
             } else {
                 move_block(map, n + r, v);
                 move_block(map, n, v);
-                map[n.y][n.x] = map[p.y][p.x];
-                map[n.y][n.x + 1] = map[p.y][p.x + 1];
-                map[p.y][p.x] = Space;
-                map[p.y][p.x + 1] = Space;
+                map[(n.x, n.y)] = map[(p.x, p.y)];
+                map[(n.x + 1, n.y)] = map[(p.x + 1, p.y)];
+                map[(p.x, p.y)] = Space;
+                map[(p.x + 1, p.y)] = Space;
             }
         }
         CrateRight => {
@@ -166,37 +135,32 @@ fn move_block(map: &mut Vec<Vec<Block>>, p: Vector2<usize>, v: Vector2<i64>) ->
         }
         Robot => {
             move_block(map, n, v);
-            map[n.y][n.x] = map[p.y][p.x];
-            map[p.y][p.x] = Space;
+            map[(n.x, n.y)] = map[(p.x, p.y)];
+            map[(p.x, p.y)] = Space;
         }
     }
 
     true
 }
 
-fn score(map: &Vec<Vec<Block>>) -> usize {
+fn score(map: &Map) -> usize {
     map.iter()
-        .enumerate()
-        .flat_map(|(y, row)| {
-            row.iter()
-                .enumerate()
-                .map(move |(x, c)| if matches!(c, Crate) || matches!(c, CrateLeft) { y * 100 + x } else { 0 })
-        })
+        .map(|((x, y), c)| if matches!(c, Crate) || matches!(c, CrateLeft) { y * 100 + x } else { 0 })
         .sum()
 }
 
-fn solve(input: &str, part2: bool) -> Option<String> {
-    let (mut map, mut pos, commands) = parse(input, part2);
+fn solve(input: &str, part2: bool) -> Result<Answer, AoCError> {
+    let (mut map, mut pos, commands) = parse(input, part2)?;
 
     for command in commands {
-        let dir = vec_for(command);
+        let dir = Point::from(command);
 
         if move_block(&mut map, pos, dir) {
             pos = (pos.cast() + dir).map(|i| i as usize);
         }
     }
 
-    Some(score(&map) as i64).map(|r| r.to_string())
+    Ok(score(&map).into())
 }
 
 impl days::Day for Day {
@@ -204,10 +168,10 @@ impl days::Day for Day {
         15
     }
 
-    fn part1(&self, input: &str) -> Option<String> {
+    fn part1(&self, input: &str) -> Result<Answer, AoCError> {
         solve(input, false)
     }
-    fn part2(&self, input: &str) -> Option<String> {
+    fn part2(&self, input: &str) -> Result<Answer, AoCError> {
         solve(input, true)
     }
 }
@@ -218,84 +182,16 @@ mod tests {
 
     const DAY: super::Day = super::Day;
     #[test]
-    fn part1_example1() {
+    fn unrecognised_map_char() {
         let text = "\
-##########
-#..O..O.O#
-#......O.#
-#.OO..O.O#
-#..O@..O.#
-#O#..O...#
-#O..O..O.#
-#.OO.O.OO#
-#....O...#
-##########
-
-<vv>^<v^>v>^vv^v>v<>v^v<v<^vv<<<^><<><>>v<vvv<>^v^>^<<<><<v<<<v^vv^v>^
-vvv<<^>^v^^><<>>><>^<<><^vv^^<>vvv<>><^^v>^>vv<>v<<<<v<^v>^<^^>>>^<v<v
-><>vv>v^v^<>><>>>><^^>vv>v<^^^>>v^v^<^^>v^^>v^<^v>v<>>v^v^<v>v^^<^^vv<
-<<v<^>>^^^^>>>v^<>vvv^><v<<<>^^^vv^<vvv>^>v<^^^^v<>^>vvvv><>>v^<<^^^^^
-^><^><>>><>^^<<^^v>>><^<v>^<vv>>v>>>^v><>^v><<<<v>>v<v<v>vvv>^<><<>^><
-^>><>^v<><^vvv<^^<><v<<<<<><^v<<<><<<^^<v<^^^><^>>^<v^><<<^>>^v<v^v<v^
->^>>^v>vv>^<<^v<>><<><<v<<v><>v<^vv<<<>^^v^>^^>>><<^v>>v^v><^^>>^<>vv^
-<><^^>^^^<><vvvvv^v<v<<>^v<v>v<<^><<><<><<<^^<<<^<<>><<><^^^>^^<>^>v<>
-^^>vv<^v^v<vv>^<><v<^v>^^^>>>^^vvv^>vvv<>>>^<^>>>>>^<<^v>^vvv<>^<><<v>
-v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^";
-        assert_eq!(DAY.part1(text), Some("10092".to_string()))
-    }
-    #[test]
-    fn part1_example2() {
-        let text = "\
-########
-#..O.O.#
-##@.O..#
-#...O..#
-#.#.O..#
-#...O..#
-#......#
-########
-
-<^^>>>vv<v>>v<<";
-        assert_eq!(DAY.part1(text), Some("2028".to_string()))
-    }
-    #[test]
-    fn part2_example1() {
-        let text = "\
-#######
-#...#.#
-#.....#
-#..OO@#
-#..O..#
-#.....#
-#######
-
-<vv<<^^<<^^";
-        assert_eq!(DAY.part2(text), Some((105 + 207 + 306).to_string()))
-    }
-    #[test]
-    fn part2_example2() {
-        let text = "\
-##########
-#..O..O.O#
-#......O.#
-#.OO..O.O#
-#..O@..O.#
-#O#..O...#
-#O..O..O.#
-#.OO.O.OO#
-#....O...#
-##########
-
-<vv>^<v^>v>^vv^v>v<>v^v<v<^vv<<<^><<><>>v<vvv<>^v^>^<<<><<v<<<v^vv^v>^
-vvv<<^>^v^^><<>>><>^<<><^vv^^<>vvv<>><^^v>^>vv<>v<<<<v<^v>^<^^>>>^<v<v
-><>vv>v^v^<>><>>>><^^>vv>v<^^^>>v^v^<^^>v^^>v^<^v>v<>>v^v^<v>v^^<^^vv<
-<<v<^>>^^^^>>>v^<>vvv^><v<<<>^^^vv^<vvv>^>v<^^^^v<>^>vvvv><>>v^<<^^^^^
-^><^><>>><>^^<<^^v>>><^<v>^<vv>>v>>>^v><>^v><<<<v>>v<v<v>vvv>^<><<>^><
-^>><>^v<><^vvv<^^<><v<<<<<><^v<<<><<<^^<v<^^^><^>>^<v^><<<^>>^v<v^v<v^
->^>>^v>vv>^<<^v<>><<><<v<<v><>v<^vv<<<>^^v^>^^>>><<^v>>v^v><^^>>^<>vv^
-<><^^>^^^<><vvvvv^v<v<<>^v<v>v<<^><<><<><<<^^<<<^<<>><<><^^^>^^<>^>v<>
-^^>vv<^v^v<vv>^<><v<^v>^^^>>>^^vvv^>vvv<>>>^<^>>>>>^<<^v>^vvv<>^<><<v>
-v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^";
-        assert_eq!(DAY.part2(text), Some("9021".to_string()))
+####
+#@x#
+####
+
+<>";
+        assert_eq!(
+            DAY.part1(text).unwrap_err().to_string(),
+            "line 2, column 3: unrecognised map char x"
+        )
     }
 }
@@ -1,141 +1,70 @@
-use crate::days;
-use num::abs;
-use pathfinding::prelude::astar;
-use priority_queue::PriorityQueue;
-use std::cmp::Reverse;
-use std::collections::{HashMap, LinkedList};
+use crate::compass::{Dir4, Point};
+use crate::days::{self, AoCError, Answer};
+use crate::graph::{self, ShortestPaths};
+use crate::grid::Grid;
+use std::collections::HashSet;
 
 pub struct Day;
 
 impl Day {}
 
-fn parse(input: &str) -> (Vec<Vec<char>>, (i32, i32), (i32, i32)) {
-    let map: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
-    let start = map
-        .iter()
-        .enumerate()
-        .find_map(|(y, line)| {
-            line.iter().enumerate().find_map(|(x, c)| {
-                if *c == 'S' {
-                    Some((x as i32, y as i32))
-                } else {
-                    None
-                }
-            })
-        })
-        .unwrap();
-    let end = map
-        .iter()
-        .enumerate()
-        .find_map(|(y, line)| {
-            line.iter().enumerate().find_map(|(x, c)| {
-                if *c == 'E' {
-                    Some((x as i32, y as i32))
-                } else {
-                    None
-                }
-            })
-        })
-        .unwrap();
+type Map = Grid<char>;
+
+fn parse(input: &str) -> Result<(Map, Point, Point), AoCError> {
+    let map = Grid::chars(input)?;
+    let find = |c: char| map.find(|&cell| cell == c).map(|(x, y)| Point::new(x as i64, y as i64));
+    let start = find('S').ok_or_else(|| AoCError::parse_at(input, input, "no start (S) on the map"))?;
+    let end = find('E').ok_or_else(|| AoCError::parse_at(input, input, "no end (E) on the map"))?;
+
+    Ok((map, start, end))
+}
 
-    (map, start, end)
+fn at(map: &Map, p: Point) -> char {
+    map[(p.x as usize, p.y as usize)]
 }
 
-fn find_shortest_route(map: &Vec<Vec<char>>, start: (i32, i32), end: (i32, i32)) -> i64 {
-    let (_, cost) = astar(
-        &(start, (1i32, 0i32)),
-        |&(p, (dx, dy))| {
-            let mut result = vec![((p, (dy, dx)), 1000), ((p, (-dy, -dx)), 1000)];
-            let advance = (p.0 + dx, (p.1 + dy));
-            if map[advance.1 as usize][advance.0 as usize] != '#' {
-                result.push(((advance, (dx, dy)), 1))
+/// A reindeer's position and heading.
+type Reindeer = (Point, Dir4);
+
+/// The cheapest routes from the start, facing east, to every position and heading.
+fn routes(map: &Map, start: Point) -> ShortestPaths<Reindeer, i64> {
+    graph::shortest_paths(
+        &|&(p, d): &Reindeer| {
+            let mut result = vec![(p, d.turn_left()), (p, d.turn_right())];
+            let advance = p + d;
+            if at(map, advance) != '#' {
+                result.push((advance, d))
             }
             result
         },
-        |(p, _)| abs(p.0 - end.0) + abs(p.1 - end.1),
-        |(p, _)| *p == end,
+        (start, Dir4::Right),
+        |a: &Reindeer, b: &Reindeer| if a.0 == b.0 { 1000 } else { 1 },
     )
-    .unwrap();
-
-    cost as i64
 }
 
-fn routes_to_goal(
-    map: &Vec<Vec<char>>,
-    max_cost: i32,
-    start: (i32, i32),
-    start_dir: (i32, i32),
-    end: (i32, i32),
-) -> Vec<Vec<bool>> {
-    let mut visited = HashMap::new();
-
-    let mut queue: PriorityQueue<((i32, i32), (i32, i32), i32), Reverse<i32>> =
-        PriorityQueue::new();
-
-    fn push(
-        queue: &mut PriorityQueue<((i32, i32), (i32, i32), i32), Reverse<i32>>,
-        p: (i32, i32),
-        d: (i32, i32),
-        cost: i32,
-        end: (i32, i32),
-    ) {
-        queue.push((p, d, cost), Reverse(abs(p.0 - end.0) + abs(p.1 - end.1)));
-    }
-
-    push(&mut queue, start, start_dir, 0, end);
-
-    while !queue.is_empty() {
-        let ((p, d, cost), _) = queue.pop().unwrap();
-
-        if cost > max_cost {
-            continue;
-        } else if map[p.1 as usize][p.0 as usize] == '#' {
-            continue;
-        } else if map[p.1 as usize][p.0 as usize] == 'E' {
-            visited.insert((p, d), cost);
-            continue;
-        } else {
-            if let Some(&old_cost) = visited.get(&(p, d)) {
-                if old_cost <= cost {
-                    continue;
-                }
-            }
-
-            visited.insert((p, d), cost);
-
-            push(&mut queue, (p.0 + d.0, p.1 + d.1), d, cost + 1, end);
-            push(&mut queue, p, (d.1, d.0), cost + 1000, end);
-            push(&mut queue, p, (-d.1, -d.0), cost + 1000, end);
-        }
-    }
-
-    let mut queue = LinkedList::new();
-    let mut result = vec![vec![false; map[0].len()]; map.len()];
-
-    for dir in [(1, 0), (0, 1), (-1, 0), (0, -1)] {
-        if visited.contains_key(&(end, dir)) {
-            queue.push_back((end, dir, max_cost));
-        }
-    }
-
-    while !queue.is_empty() {
-        let (p, d, cost) = queue.pop_back().unwrap();
-
-        match visited.get(&(p, d)) {
-            Some(&other_cost) => {
-                if other_cost == cost {
-                    result[p.1 as usize][p.0 as usize] = true;
-
-                    queue.push_back(((p.0 - d.0, p.1 - d.1), d, cost - 1));
-                    queue.push_back((p, (d.1, d.0), cost - 1000));
-                    queue.push_back((p, (-d.1, -d.0), cost - 1000));
-                }
-            }
-            None => {}
-        }
-    }
-
-    result
+/// The cost of the cheapest route to the end, and the headings it can arrive with.
+fn best_at_end(
+    routes: &ShortestPaths<Reindeer, i64>,
+    end: Point,
+) -> Result<(i64, Vec<Reindeer>), AoCError> {
+    let arrivals = Dir4::ALL
+        .into_iter()
+        .filter_map(|d| Some(((end, d), routes.cost(&(end, d))?)))
+        .collect::<Vec<_>>();
+    let cost = arrivals
+        .iter()
+        .map(|&(_, cost)| cost)
+        .min()
+        .ok_or_else(|| AoCError::unsolvable("the end cannot be reached"))?;
+
+    Ok((
+        cost,
+        arrivals
+            .into_iter()
+            .filter(|&(_, c)| c == cost)
+            .map(|(reindeer, _)| reindeer)
+            .collect(),
+    ))
 }
 
 impl days::Day for Day {
@@ -143,134 +72,23 @@ impl days::Day for Day {
         16
     }
 
-    fn part1(&self, input: &str) -> Option<String> {
-        let (map, start, end) = parse(input);
-        Some(find_shortest_route(&map, start, end)).map(|r| r.to_string())
+    fn part1(&self, input: &str) -> Result<Answer, AoCError> {
+        let (map, start, end) = parse(input)?;
+        let (cost, _) = best_at_end(&routes(&map, start), end)?;
+        Ok(cost.into())
     }
-    fn part2(&self, input: &str) -> Option<String> {
-        let (map, start, end) = parse(input);
-
-        let max_cost = find_shortest_route(&map, start, end);
+    fn part2(&self, input: &str) -> Result<Answer, AoCError> {
+        let (map, start, end) = parse(input)?;
 
-        let mut visited = routes_to_goal(&map, max_cost as i32, start, (1, 0), end);
+        let routes = routes(&map, start);
+        let (_, arrivals) = best_at_end(&routes, end)?;
 
-        visited[start.1 as usize][start.0 as usize] = true;
-        visited[end.1 as usize][end.0 as usize] = true;
+        let tiles = routes
+            .nodes_on_paths_to(arrivals)
+            .into_iter()
+            .map(|(p, _)| p)
+            .collect::<HashSet<_>>();
 
-        Some(
-            visited
-                .iter()
-                .map(|row| row.iter().filter(|x| **x).count() as i64)
-                .sum::<i64>(),
-        ).map(|r| r.to_string())
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::days::Day;
-
-    const DAY: super::Day = super::Day;
-    #[test]
-    fn part1_example1() {
-        let text = "\
-###############
-#.......#....E#
-#.#.###.#.###.#
-#.....#.#...#.#
-#.###.#####.#.#
-#.#.#.......#.#
-#.#.#####.###.#
-#...........#.#
-###.#.#####.#.#
-#...#.....#.#.#
-#.#.#.###.#.#.#
-#.....#...#.#.#
-#.###.#.#.#.#.#
-#S..#.....#...#
-###############";
-        assert_eq!(DAY.part1(text), Some("7036".to_string()))
-    }
-    #[test]
-    fn part1_example2() {
-        let text = "\
-#################
-#...#...#...#..E#
-#.#.#.#.#.#.#.#.#
-#.#.#.#...#...#.#
-#.#.#.#.###.#.#.#
-#...#.#.#.....#.#
-#.#.#.#.#.#####.#
-#.#...#.#.#.....#
-#.#.#####.#.###.#
-#.#.#.......#...#
-#.#.###.#####.###
-#.#.#...#.....#.#
-#.#.#.#####.###.#
-#.#.#.........#.#
-#.#.#.#########.#
-#S#.............#
-#################";
-        assert_eq!(DAY.part1(text), Some("11048".to_string()))
-    }
-    #[test]
-    fn part2_example1() {
-        let text = "\
-###############
-#.......#....E#
-#.#.###.#.###.#
-#.....#.#...#.#
-#.###.#####.#.#
-#.#.#.......#.#
-#.#.#####.###.#
-#...........#.#
-###.#.#####.#.#
-#...#.....#.#.#
-#.#.#.###.#.#.#
-#.....#...#.#.#
-#.###.#.#.#.#.#
-#S..#.....#...#
-###############";
-        assert_eq!(DAY.part2(text), Some("45".to_string()))
-    }
-    #[test]
-    fn part2_example2() {
-        let text = "\
-#################
-#...#...#...#..E#
-#.#.#.#.#.#.#.#.#
-#.#.#.#...#...#.#
-#.#.#.#.###.#.#.#
-#...#.#.#.....#.#
-#.#.#.#.#.#####.#
-#.#...#.#.#.....#
-#.#.#####.#.###.#
-#.#.#.......#...#
-#.#.###.#####.###
-#.#.#...#.....#.#
-#.#.#.#####.###.#
-#.#.#.........#.#
-#.#.#.#########.#
-#S#.............#
-#################";
-        assert_eq!(DAY.part2(text), Some("64".to_string()))
-    }
-    #[test]
-    fn part2_small() {
-        let text = "\
-####
-#.E#
-#S.#
-####";
-        assert_eq!(DAY.part2(text), Some("3".to_string()))
-    }
-    #[test]
-    fn part2_small2() {
-        let text = "\
-#####
-#..E#
-#S.##
-#####";
-        assert_eq!(DAY.part2(text), Some("5".to_string()))
+        Ok(tiles.len().into())
     }
 }
@@ -1,39 +1,43 @@
-use crate::days;
-use itertools::Itertools;
+use crate::cancel;
+use crate::days::{self, AoCError, Answer};
+use crate::parsing;
 use num::pow;
-use regex::Regex;
 
 pub struct Day;
 
 impl Day {}
 
+/// The registers, instruction pointer and output of the 3-bit computer.
 #[derive(Debug, Eq, PartialEq)]
-struct Machine {
-    a: i64,
-    b: i64,
-    c: i64,
-    i: i64,
-    output: Vec<i64>,
+pub struct Machine {
+    pub a: i64,
+    pub b: i64,
+    pub c: i64,
+    pub i: i64,
+    pub output: Vec<i64>,
 }
 
 impl Machine {
-    fn combo(&self, operand: i64) -> i64 {
+    fn combo(&self, operand: i64) -> Result<i64, AoCError> {
         if operand <= 3 {
-            operand
+            Ok(operand)
         } else if operand == 4 {
-            self.a
+            Ok(self.a)
         } else if operand == 5 {
-            self.b
+            Ok(self.b)
         } else if operand == 6 {
-            self.c
+            Ok(self.c)
         } else {
-            panic!("Invalid combo operand: {operand}");
+            Err(AoCError::unsolvable(format!(
+                "invalid combo operand {operand} at instruction {}",
+                self.i
+            )))
         }
     }
 }
 
 impl Machine {
-    fn new(a: i64, b: i64, c: i64) -> Machine {
+    pub fn new(a: i64, b: i64, c: i64) -> Machine {
         Machine {
             a,
             b,
@@ -57,21 +61,24 @@ enum OpCode {
 }
 
 impl OpCode {
-    fn execute(&self, machine: &mut Machine, operand: i64) {
+    fn execute(&self, machine: &mut Machine, operand: i64) -> Result<(), AoCError> {
         match self {
-            OpCode::ADV => machine.a = machine.a / pow(2, machine.combo(operand) as usize),
+            OpCode::ADV => machine.a = machine.a / pow(2, machine.combo(operand)? as usize),
             OpCode::BXL => machine.b = machine.b ^ operand,
-            OpCode::BST => machine.b = machine.combo(operand) & 0x7,
+            OpCode::BST => machine.b = machine.combo(operand)? & 0x7,
             OpCode::JNZ => {
                 if machine.a != 0 {
+                    // a program can loop forever, so give up when the runner does
+                    cancel::check()?;
                     machine.i = operand - 2
                 }
             }
             OpCode::BXC => machine.b = machine.b ^ machine.c,
-            OpCode::OUT => machine.output.push(machine.combo(operand) & 0x7),
-            OpCode::BDV => machine.b = machine.a / pow(2, machine.combo(operand) as usize),
-            OpCode::CDV => machine.c = machine.a / pow(2, machine.combo(operand) as usize),
+            OpCode::OUT => machine.output.push(machine.combo(operand)? & 0x7),
+            OpCode::BDV => machine.b = machine.a / pow(2, machine.combo(operand)? as usize),
+            OpCode::CDV => machine.c = machine.a / pow(2, machine.combo(operand)? as usize),
         }
+        Ok(())
     }
 }
 
@@ -85,56 +92,55 @@ fn opcode_for(n: i64) -> OpCode {
         5 => OpCode::OUT,
         6 => OpCode::BDV,
         7 => OpCode::CDV,
-        _ => panic!("Unexpected opcode: {n}"),
+        _ => unreachable!("programs are checked for 3-bit values when parsed"),
     }
 }
 
-fn parse_register(input: &str) -> i64 {
-    Regex::new(r"Register \w: (\d+)")
-        .ok()
-        .and_then(|r| r.captures(input))
-        .and_then(|c| c.get(1))
-        .and_then(|v| v.as_str().parse().ok())
-        .unwrap()
+fn parse_register(input: &str, line: &str) -> Result<i64, AoCError> {
+    let [_, value] = parsing::fields(input, line, "Register {name}: {value}")?;
+    days::parse(input, value)
 }
 
-fn parse_program(input: &str) -> Vec<i64> {
-    Regex::new(r"Program: (.*)")
-        .ok()
-        .and_then(|r| r.captures(input))
-        .and_then(|c| c.get(1))
-        .map(|v| {
-            v.as_str()
-                .split(",")
-                .map(|o| o.parse::<i64>().expect(&format!("Failed to parse {o}")))
-                .collect()
+fn parse_program(input: &str, line: &str) -> Result<Vec<i64>, AoCError> {
+    let [program] = parsing::fields(input, line, "Program: {values}")?;
+
+    program
+        .split(",")
+        .map(|o| {
+            let value = days::parse(input, o)?;
+            if (0..=7).contains(&value) {
+                Ok(value)
+            } else {
+                Err(AoCError::parse_at(input, o, format!("{value} is not a 3-bit number")))
+            }
         })
-        .unwrap()
+        .collect()
 }
 
-fn parse(input: &str) -> (i64, i64, i64, Vec<i64>) {
-    let mut lines = input.lines();
-
-    let a = parse_register(lines.next().unwrap());
-    let b = parse_register(lines.next().unwrap());
-    let c = parse_register(lines.next().unwrap());
-
-    let _ = lines.next();
-
-    let program = parse_program(lines.next().unwrap());
-
-    (a, b, c, program)
+/// The initial A, B and C registers and the program.
+pub fn parse(input: &str) -> Result<(i64, i64, i64, Vec<i64>), AoCError> {
+    let (registers, program) =
+        parsing::two_sections(input, "registers, a blank line, then the program")?;
+    let mut lines = registers.lines();
+    let a = parse_register(input, parsing::next_line(registers, &mut lines))?;
+    let b = parse_register(input, parsing::next_line(registers, &mut lines))?;
+    let c = parse_register(input, parsing::next_line(registers, &mut lines))?;
+    let program = parse_program(input, program.trim_end())?;
+
+    Ok((a, b, c, program))
 }
 
-fn run(machine: &mut Machine, input: &Vec<i64>) {
-    while machine.i < input.len() as i64 {
-        opcode_for(input[machine.i as usize]).execute(machine, input[(machine.i + 1) as usize]);
+/// Runs `input` on `machine` until it halts.
+pub fn run(machine: &mut Machine, input: &Vec<i64>) -> Result<(), AoCError> {
+    while machine.i + 1 < input.len() as i64 {
+        opcode_for(input[machine.i as usize]).execute(machine, input[(machine.i + 1) as usize])?;
 
         machine.i += 2;
     }
+    Ok(())
 }
 
-fn solve_part_2(input: &Vec<i64>, expected: &Vec<i64>) -> Option<i64> {
+fn solve_part_2(input: &Vec<i64>, expected: &Vec<i64>) -> Result<i64, AoCError> {
 
     let mut queue = vec![];
     for i in (0..=0x7).rev() {
@@ -147,13 +153,13 @@ fn solve_part_2(input: &Vec<i64>, expected: &Vec<i64>) -> Option<i64> {
 
         let mut machine = Machine::new(a, 0, 0);
 
-        run(&mut machine, input);
+        run(&mut machine, input)?;
 
         if machine.output.len() > 0 && machine.output.len() <= expected.len()
             && machine.output[..] == expected[expected.len() - machine.output.len()..]
         {
             if machine.output.len() == expected.len() {
-                return Some(a);
+                return Ok(a);
             } else {
                 for i in (0..=0x7).rev() {
                     if a > 0 || i > 0 {
@@ -164,7 +170,7 @@ fn solve_part_2(input: &Vec<i64>, expected: &Vec<i64>) -> Option<i64> {
         }
     }
 
-    return None;
+    Err(AoCError::unsolvable("no value of A makes the program output itself"))
 }
 
 impl days::Day for Day {
@@ -172,8 +178,8 @@ impl days::Day for Day {
         17
     }
 
-    fn part1(&self, input: &str) -> Option<String> {
-        let (a, b, c, program) = parse(input);
+    fn part1(&self, input: &str) -> Result<Answer, AoCError> {
+        let (a, b, c, program) = parse(input)?;
 
         let mut machine = Machine {
             a,
@@ -183,14 +189,14 @@ impl days::Day for Day {
             output: vec![],
         };
 
-        run(&mut machine, &program);
+        run(&mut machine, &program)?;
 
-        Some(machine.output.iter().map(|n| n.to_string()).join(","))
+        Ok(Answer::list(machine.output))
     }
-    fn part2(&self, input: &str) -> Option<String> {
-        let (_, _, _, program) = parse(input);
+    fn part2(&self, input: &str) -> Result<Answer, AoCError> {
+        let (_, _, _, program) = parse(input)?;
 
-        solve_part_2(&program, &program).map(|r| r.to_string())
+        solve_part_2(&program, &program).map(Answer::from)
     }
 }
 
@@ -203,27 +209,44 @@ mod tests {
     #[test]
     fn part1_example0() {
         let mut machine = Machine::new(0, 2024, 43690);
-        opcode_for(4).execute(&mut machine, 0);
+        opcode_for(4).execute(&mut machine, 0).unwrap();
         assert_eq!(machine.b, 44354)
     }
     #[test]
-    fn part1_example1() {
+    fn invalid_combo_operand() {
         let text = "\
-Register A: 729
+Register A: 1
 Register B: 0
 Register C: 0
 
-Program: 0,1,5,4,3,0";
-        assert_eq!(DAY.part1(text), Some("4,6,3,5,6,3,5,2,1,0".to_string()))
+Program: 5,7";
+        assert!(matches!(DAY.part1(text), Err(AoCError::Unsolvable(_))));
     }
     #[test]
-    fn part2_example1() {
+    fn invalid_program_value() {
         let text = "\
-Register A: 2024
+Register A: 1
 Register B: 0
 Register C: 0
 
-Program: 0,3,5,4,3,0";
-        assert_eq!(DAY.part2(text), Some("117440".to_string()))
+Program: 0,8";
+        assert_eq!(
+            DAY.part1(text).unwrap_err().to_string(),
+            "line 5, column 12: 8 is not a 3-bit number"
+        );
+    }
+    #[test]
+    fn endless_program_is_cancelled() {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let result = cancel::run(Some(std::time::Duration::from_millis(20)), move || {
+            // jumps back to the start for as long as A isn't 0
+            let outcome = run(&mut Machine::new(1, 0, 0), &vec![3, 0]);
+            sender.send(outcome.unwrap_err().to_string()).unwrap();
+        });
+        assert!(matches!(result, Err(AoCError::Timeout(_))));
+        assert_eq!(
+            receiver.recv_timeout(std::time::Duration::from_secs(5)).unwrap(),
+            "cancelled"
+        );
     }
 }
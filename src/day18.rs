@@ -1,87 +1,89 @@
-use crate::days;
-use num::abs;
-use pathfinding::prelude::astar;
+use crate::days::{self, AoCError, Answer, Param, Params};
+use crate::graph;
+use crate::grid::Grid;
 
 pub struct Day;
 
 impl Day {}
 
-fn parse(input: &str) -> (Vec<(usize, usize)>, (usize, usize)) {
+type Coord = (usize, usize);
+
+fn parse(input: &str) -> Result<Vec<Coord>, AoCError> {
     let coords = input
         .lines()
         .map(|line| {
-            let mut parts = line.split(',');
-            (
-                parts.next().unwrap().parse().unwrap(),
-                parts.next().unwrap().parse().unwrap(),
-            )
+            let (x, y) = line
+                .split_once(',')
+                .ok_or_else(|| AoCError::parse_at(input, line, "expected <x>,<y>"))?;
+            Ok((days::parse(input, x)?, days::parse(input, y)?))
         })
-        .collect::<Vec<_>>();
+        .collect::<Result<Vec<_>, AoCError>>()?;
 
-    let max_x = coords.iter().map(|c| c.0).max().unwrap();
-    let max_y = coords.iter().map(|c| c.1).max().unwrap();
+    if coords.is_empty() {
+        return Err(AoCError::parse_at(input, input, "no coordinates"));
+    }
+    Ok(coords)
+}
 
-    (coords, (max_x, max_y))
+/// The bottom right corner of a memory space whose largest coordinate is `size`, checking that
+/// every byte falls within it.
+fn corner(coords: &[Coord], size: usize) -> Result<Coord, AoCError> {
+    match coords.iter().find(|c| c.0 > size || c.1 > size) {
+        Some(c) => Err(AoCError::InvalidParameter(
+            SIZE.name.to_string(),
+            format!("{},{} is outside a memory space of size {size}", c.0, c.1),
+        )),
+        None => Ok((size, size)),
+    }
 }
 
-fn corrupt_memory(coords: &Vec<(usize, usize)>, max: (usize, usize), n: usize) -> Vec<Vec<bool>> {
-    let mut result = vec![vec![false; max.0 + 1]; max.1 + 1];
+fn corrupt_memory(coords: &Vec<(usize, usize)>, max: (usize, usize), n: usize) -> Grid<bool> {
+    let mut result = Grid::new(max.0 + 1, max.1 + 1, false);
 
     for i in 0..n {
-        let coord = coords[i];
-
-        result[coord.1][coord.0] = true;
+        result[coords[i]] = true;
     }
 
     result
 }
 
-fn cost_of(map: &Vec<Vec<bool>>, max: (usize, usize)) -> Option<i64> {
-    if let Some((_, cost)) = astar(
-        &(0, 0),
-        |&(x, y)| {
-            let mut result = vec![];
-            if x > 0 && !map[y][x - 1] {
-                result.push(((x - 1, y), 1))
-            }
-            if x < max.0 && !map[y][x + 1] {
-                result.push(((x + 1, y), 1))
-            }
-            if y > 0 && !map[y - 1][x] {
-                result.push(((x, y - 1), 1))
-            }
-            if y < max.1 && !map[y + 1][x] {
-                result.push(((x, y + 1), 1))
-            }
-            result
-        },
-        |&(x, y)| abs((max.0 as i64) - (x as i64)) + abs((max.1 as i64) - (y as i64)),
-        |&p| p == max,
-    ) {
-        Some(cost)
-    } else {
-        None
-    }
+fn cost_of(map: &Grid<bool>, max: (usize, usize)) -> Option<i64> {
+    let open = |&p: &Coord| map.neighbours4(p).filter(|&next| !map[next]);
+    graph::bfs(&open, (0, 0)).get(&max).map(|&steps| steps as i64)
 }
 
-fn do_part1(input: &str, n: usize) -> Option<i64> {
-    let (coords, max) = parse(input);
+fn do_part1(input: &str, size: usize, n: usize) -> Result<i64, AoCError> {
+    let coords = parse(input)?;
+    let max = corner(&coords, size)?;
 
-    let map = corrupt_memory(&coords, max, n);
+    let map = corrupt_memory(&coords, max, n.min(coords.len()));
 
-    cost_of(&map, max)
+    cost_of(&map, max).ok_or_else(|| AoCError::unsolvable("the exit cannot be reached"))
 }
 
+const SIZE: Param = Param::new("size", "70", "largest coordinate in the memory space");
+const BYTES: Param = Param::new("bytes", "1024", "bytes that have fallen in part 1");
+
 impl days::Day for Day {
     fn day(&self) -> u32 {
         18
     }
 
-    fn part1(&self, input: &str) -> Option<String> {
-        do_part1(input, 1024).map(|r| r.to_string())
+    fn part1(&self, input: &str) -> Result<Answer, AoCError> {
+        self.part1_with(input, &Params::default())
+    }
+    fn part2(&self, input: &str) -> Result<Answer, AoCError> {
+        self.part2_with(input, &Params::default())
+    }
+    fn params(&self) -> &'static [Param] {
+        &[SIZE, BYTES]
     }
-    fn part2(&self, input: &str) -> Option<String> {
-        let (coords, max) = parse(input);
+    fn part1_with(&self, input: &str, params: &Params) -> Result<Answer, AoCError> {
+        do_part1(input, params.get(&SIZE, 1)?, params.get(&BYTES, 1)?).map(Answer::from)
+    }
+    fn part2_with(&self, input: &str, params: &Params) -> Result<Answer, AoCError> {
+        let coords = parse(input)?;
+        let max = corner(&coords, params.get(&SIZE, 2)?)?;
 
         let mut bottom = 0;
         let mut top = coords.len();
@@ -97,7 +99,8 @@ impl days::Day for Day {
             }
 
             if top == bottom + 1 {
-                return Some(format!("{},{}", coords[bottom].0, coords[bottom].1));
+                let (x, y) = coords[bottom];
+                return Ok(Answer::Coordinate(x as i64, y as i64));
             }
         }
     }
@@ -105,67 +108,11 @@ impl days::Day for Day {
 
 #[cfg(test)]
 mod tests {
-    use crate::days::Day;
-
-    const DAY: super::Day = super::Day;
-    #[test]
-    fn part1_example1() {
-        let text = "\
-5,4
-4,2
-4,5
-3,0
-2,1
-6,3
-2,4
-1,5
-0,6
-3,3
-2,6
-5,1
-1,2
-5,5
-2,5
-6,5
-1,4
-0,4
-6,4
-1,1
-6,1
-1,0
-0,5
-1,6
-2,0";
-        assert_eq!(super::do_part1(text, 12), Some(22))
-    }
     #[test]
-    fn part2_example1() {
-        let text = "\
-5,4
-4,2
-4,5
-3,0
-2,1
-6,3
-2,4
-1,5
-0,6
-3,3
-2,6
-5,1
-1,2
-5,5
-2,5
-6,5
-1,4
-0,4
-6,4
-1,1
-6,1
-1,0
-0,5
-1,6
-2,0";
-        assert_eq!(DAY.part2(text), Some("6,1".to_string()))
+    fn outside_memory_space() {
+        assert_eq!(
+            super::do_part1("1,2\n7,3", 6, 2).unwrap_err().to_string(),
+            "invalid parameter size: 7,3 is outside a memory space of size 6"
+        );
     }
 }
@@ -1,27 +1,38 @@
-use crate::days;
+use crate::days::{self, AoCError, Answer};
+use crate::memo::Memo;
 use itertools::Itertools;
 use regex::Regex;
 use std::cmp::min;
-use std::collections::{HashMap, HashSet};
+use std::collections::HashSet;
 
 pub struct Day;
 
 impl Day {}
 
-fn parse(input: &str) -> (Vec<&str>, Vec<&str>) {
+fn parse(input: &str) -> Result<(Vec<&str>, Vec<&str>), AoCError> {
     let mut lines = input.lines();
 
-    let towels: Vec<&str> = lines.next().unwrap().split(", ").collect();
+    let towels: Vec<&str> = lines.next().unwrap_or(input).split(", ").collect();
+    if let Some(towel) = towels
+        .iter()
+        .find(|towel| towel.is_empty() || !towel.chars().all(|c| c.is_ascii_lowercase()))
+    {
+        return Err(AoCError::parse_at(
+            input,
+            towel,
+            format!("'{towel}' is not a towel pattern"),
+        ));
+    }
 
     let _ = lines.next();
 
     let designs = lines.sorted().collect();
 
-    (towels, designs)
+    Ok((towels, designs))
 }
 
 fn count_combinations<'a>(
-    cache: &mut HashMap<&'a str, usize>,
+    memo: &Memo<&'a str, usize>,
     towels: &HashSet<&str>,
     min_towel: usize,
     max_towel: usize,
@@ -30,22 +41,17 @@ fn count_combinations<'a>(
     if design.is_empty() {
         1
     } else {
-        match cache.get(design) {
-            Some(result) => *result,
-            None => {
-                let mut result = 0;
-
-                for len in min_towel..=min(max_towel, design.len()) {
-                    if towels.contains(&design[0..len]) {
-                        result +=
-                            count_combinations(cache, towels, min_towel, max_towel, &design[len..])
-                    }
-                }
-                cache.insert(design, result);
+        memo.get_or_insert_with(design, || {
+            let mut result = 0;
 
-                result
+            for len in min_towel..=min(max_towel, design.len()) {
+                if towels.contains(&design[0..len]) {
+                    result += count_combinations(memo, towels, min_towel, max_towel, &design[len..])
+                }
             }
-        }
+
+            result
+        })
     }
 }
 
@@ -54,77 +60,41 @@ impl days::Day for Day {
         19
     }
 
-    fn part1(&self, input: &str) -> Option<String> {
-        let (towels, designs) = parse(input);
+    fn part1(&self, input: &str) -> Result<Answer, AoCError> {
+        let (towels, designs) = parse(input)?;
         let towels = Regex::new(&format!("^({})+$", towels.iter().join("|"))).unwrap();
 
-        Some(
-            designs
-                .iter()
-                .filter(|design| towels.is_match(design))
-                .count(),
-        )
-        .map(|r| r.to_string())
+        Ok(designs
+            .iter()
+            .filter(|design| towels.is_match(design))
+            .count()
+            .into())
     }
-    fn part2(&self, input: &str) -> Option<String> {
-        let (towels, designs) = parse(input);
+    fn part2(&self, input: &str) -> Result<Answer, AoCError> {
+        let (towels, designs) = parse(input)?;
         let towels = HashSet::from_iter(towels);
 
         let min_towel = towels.iter().map(|towel| towel.len()).min().unwrap();
         let max_towel = towels.iter().map(|towel| towel.len()).max().unwrap();
 
-        Some(
-            designs
-                .iter()
-                .map(|design| {
-                    count_combinations(&mut HashMap::new(), &towels, min_towel, max_towel, design)
-                })
-                .sum::<usize>(),
-        )
-        .map(|r| r.to_string())
+        // how a design's tail can be made doesn't depend on the rest of it, so designs share one
+        let memo = Memo::new();
+        Ok(designs
+            .iter()
+            .map(|design| count_combinations(&memo, &towels, min_towel, max_towel, design))
+            .sum::<usize>()
+            .into())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::days::Day;
 
-    const DAY: super::Day = super::Day;
-    #[test]
-    fn part1_example1() {
-        let text = "\
-r, wr, b, g, bwu, rb, gb, br
-
-brwrr
-bggr
-gbbr
-rrbgbr
-ubwu
-bwurrg
-brgr
-bbrgwb";
-        assert_eq!(DAY.part1(text), Some(6.to_string()))
-    }
-    #[test]
-    fn part2_example1() {
-        let text = "\
-r, wr, b, g, bwu, rb, gb, br
-
-brwrr
-bggr
-gbbr
-rrbgbr
-ubwu
-bwurrg
-brgr
-bbrgwb";
-        assert_eq!(DAY.part2(text), Some(16.to_string()))
-    }
     #[test]
     fn count_combinations_short() {
         assert_eq!(
-            count_combinations(&mut HashMap::new(), &HashSet::from(["a"]), 1, 1, "a"),
+            count_combinations(&Memo::new(), &HashSet::from(["a"]), 1, 1, "a"),
             1
         );
     }
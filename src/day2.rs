@@ -1,9 +1,23 @@
-use crate::days;
+use crate::days::{self, AoCError, Answer};
 
 pub struct Day;
 
 impl Day {}
 
+fn parse(input: &str) -> Result<Vec<Vec<i64>>, AoCError> {
+    input
+        .split_terminator("\n")
+        .map(|line| {
+            if line.trim().is_empty() {
+                return Err(AoCError::parse_at(input, line, "empty report"));
+            }
+            line.split_whitespace()
+                .map(|n| days::parse(input, n))
+                .collect()
+        })
+        .collect()
+}
+
 fn check_report(report: &Vec<i64>) -> bool {
     let mut direction = Direction::Unknown;
     let mut i = report.iter();
@@ -58,29 +72,15 @@ impl days::Day for Day {
         2
     }
 
-    fn part1(&self, input: &str) -> Option<String> {
-        let reports = input
-            .split_terminator("\n")
-            .map(|line| {
-                line.split_whitespace()
-                    .map(|n| n.parse().unwrap())
-                    .collect()
-            })
-            .collect::<Vec<Vec<i64>>>();
+    fn part1(&self, input: &str) -> Result<Answer, AoCError> {
+        let reports = parse(input)?;
 
-        Some(reports.iter().filter(|r| check_report(r)).count() as i64).map(|r| r.to_string())
+        Ok(reports.iter().filter(|r| check_report(r)).count().into())
     }
-    fn part2(&self, input: &str) -> Option<String> {
-        let reports = input
-            .split_terminator("\n")
-            .map(|line| {
-                line.split_whitespace()
-                    .map(|n| n.parse().unwrap())
-                    .collect()
-            })
-            .collect::<Vec<Vec<i64>>>();
+    fn part2(&self, input: &str) -> Result<Answer, AoCError> {
+        let reports = parse(input)?;
 
-        Some(
+        Ok(
             reports
                 .iter()
                 .filter(|report| {
@@ -103,39 +103,8 @@ impl days::Day for Day {
 
                     return false;
                 })
-                .count() as i64,
-        ).map(|r| r.to_string())
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::days::Day;
-
-    const DAY: crate::day2::Day = crate::day2::Day;
-    #[test]
-    fn part1_example1() {
-        let text = "7 6 4 2 1
-1 2 7 8 9
-9 7 6 2 1
-1 3 2 4 5
-8 6 4 4 1
-1 3 6 7 9";
-        assert_eq!(DAY.part1(text), Some("2".to_string()))
-    }
-    #[test]
-    fn part2_example1() {
-        let text = "7 6 4 2 1
-1 2 7 8 9
-9 7 6 2 1
-1 3 2 4 5
-8 6 4 4 1
-1 3 6 7 9";
-        assert_eq!(DAY.part2(text), Some("4".to_string()))
-    }
-    #[test]
-    fn part2_remove_first() {
-        let text = "1 8 9 10 11";
-        assert_eq!(DAY.part2(text), Some("1".to_string()))
+                .count()
+                .into(),
+        )
     }
 }
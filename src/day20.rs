@@ -1,83 +1,47 @@
-use crate::days;
+use crate::days::{self, AoCError, Answer, Param, Params};
+use crate::graph;
+use crate::grid::{Grid, Pos};
 use num::abs;
-use std::collections::VecDeque;
 
 pub struct Day;
 
 impl Day {}
 
-fn parse(input: &str) -> (Vec<Vec<bool>>, (usize, usize), (usize, usize)) {
-    let map: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
+type Map = Grid<bool>;
+
+fn parse(input: &str) -> Result<(Map, Pos, Pos), AoCError> {
+    let map = Grid::chars(input)?;
     let start = map
-        .iter()
-        .enumerate()
-        .find_map(|(y, line)| {
-            line.iter().enumerate().find_map(|(x, c)| {
-                if *c == 'S' {
-                    Some((x, y))
-                } else {
-                    None
-                }
-            })
-        })
-        .unwrap();
+        .find(|&c| c == 'S')
+        .ok_or_else(|| AoCError::parse_at(input, input, "no start (S) on the map"))?;
     let end = map
-        .iter()
-        .enumerate()
-        .find_map(|(y, line)| {
-            line.iter().enumerate().find_map(|(x, c)| {
-                if *c == 'E' {
-                    Some((x, y))
-                } else {
-                    None
-                }
-            })
-        })
-        .unwrap();
+        .find(|&c| c == 'E')
+        .ok_or_else(|| AoCError::parse_at(input, input, "no end (E) on the map"))?;
 
-    let map = map
-        .iter()
-        .map(|row| row.iter().map(|c| *c == '#').collect())
-        .collect();
-
-    (map, start, end)
+    Ok((map.map(|&c| c == '#'), start, end))
 }
 
 const WALL_COST: i64 = i32::MAX as i64;
 
-fn calculate_costs(map: &Vec<Vec<bool>>, start: (usize, usize)) -> Vec<Vec<i64>> {
-    let mut queue = VecDeque::new();
-    queue.push_back((start, 0));
-
-    let mut result = vec![vec![WALL_COST; map[0].len()]; map.len()];
-
-    while !queue.is_empty() {
-        let ((x, y), cost) = queue.pop_front().unwrap();
+fn calculate_costs(map: &Map, start: Pos) -> Grid<i64> {
+    let open = |&p: &Pos| map.neighbours4(p).filter(|&next| !map[next]);
 
-        if !map[y][x] && result[y][x] > cost {
-            result[y][x] = cost;
-            queue.push_back(((x + 1, y), cost + 1));
-            queue.push_back(((x - 1, y), cost + 1));
-            queue.push_back(((x, y + 1), cost + 1));
-            queue.push_back(((x, y - 1), cost + 1));
-        }
+    let mut result = Grid::new(map.width(), map.height(), WALL_COST);
+    for (p, cost) in graph::bfs(&open, start) {
+        result[p] = cost as i64;
     }
 
     result
 }
 
-fn get<T: Copy>(map: &Vec<Vec<T>>, x: i64, y: i64, default: T) -> T {
-    if x < 0 || y < 0 || y >= map.len() as i64 || x >= map[0].len() as i64 {
-        default
-    } else {
-        map[y as usize][x as usize]
-    }
+fn is_wall(map: &Map, x: i64, y: i64) -> bool {
+    map.get(x, y).copied().unwrap_or(true)
 }
 
 fn find_modern_cheats_from<F>(
-    map: &Vec<Vec<bool>>,
-    cost_from_start: &Vec<Vec<i64>>,
-    cost_to_end: &Vec<Vec<i64>>,
+    map: &Map,
+    cost_from_start: &Grid<i64>,
+    cost_to_end: &Grid<i64>,
     x: i64,
     y: i64,
     max_cheat: i64,
@@ -85,15 +49,15 @@ fn find_modern_cheats_from<F>(
 ) where
     F: FnMut(i64),
 {
-    let cost_to_here = cost_from_start[y as usize][x as usize];
+    let cost_to_here = cost_from_start[(x as usize, y as usize)];
 
     for dy in -max_cheat..=max_cheat {
         let max_dx = max_cheat - abs(dy);
         for dx in -max_dx..=max_dx {
             if dx != 0 || dy != 0 {
                 let (nx, ny) = (x + dx, y + dy);
-                if !get(map, nx, ny, true) {
-                    let cost_from_here = cost_to_end[ny as usize][nx as usize];
+                if !is_wall(map, nx, ny) {
+                    let cost_from_here = cost_to_end[(nx as usize, ny as usize)];
                     let cheat_time = abs(dx) + abs(dy);
                     callback(cost_to_here + cheat_time + cost_from_here);
                 }
@@ -103,16 +67,16 @@ fn find_modern_cheats_from<F>(
 }
 
 fn find_modern_cheats(
-    map: &Vec<Vec<bool>>,
-    cost_from_start: &Vec<Vec<i64>>,
-    cost_to_end: &Vec<Vec<i64>>,
+    map: &Map,
+    cost_from_start: &Grid<i64>,
+    cost_to_end: &Grid<i64>,
     max_cheat: i64,
 ) -> Vec<i64> {
     let mut result = vec![];
 
-    for y in 1..(map.len() as i64 - 1) {
-        for x in 1..(map[y as usize].len() as i64 - 1) {
-            if !get(map, x, y, true) {
+    for y in 1..(map.height() as i64 - 1) {
+        for x in 1..(map.width() as i64 - 1) {
+            if !is_wall(map, x, y) {
                 find_modern_cheats_from(
                     map,
                     cost_from_start,
@@ -130,17 +94,17 @@ fn find_modern_cheats(
 }
 
 fn count_cheats(
-    map: &Vec<Vec<bool>>,
-    start: (usize, usize),
-    end: (usize, usize),
+    map: &Map,
+    start: Pos,
+    end: Pos,
     saving: i64,
     max_cheat: i64,
 ) -> usize {
     let costs_from_start = calculate_costs(map, start);
     let costs_to_end = calculate_costs(map, end);
 
-    let normal_cost = costs_from_start[end.1][end.0];
-    assert_eq!(normal_cost, costs_to_end[start.1][start.0]);
+    let normal_cost = costs_from_start[end];
+    assert_eq!(normal_cost, costs_to_end[start]);
 
     let cheats = find_modern_cheats(map, &costs_from_start, &costs_to_end, max_cheat);
 
@@ -152,109 +116,31 @@ fn count_cheats(
         .count()
 }
 
+const THRESHOLD: Param = Param::new("threshold", "100", "least time a cheat must save");
+const CHEAT: Param = Param::per_part("cheat", "2", "20", "most picoseconds a cheat can last");
+
 impl days::Day for Day {
     fn day(&self) -> u32 {
         20
     }
 
-    fn part1(&self, input: &str) -> Option<String> {
-        let (map, start, end) = parse(input);
-        Some(count_cheats(&map, start, end, 100, 2)).map(|r| r.to_string())
-    }
-    fn part2(&self, input: &str) -> Option<String> {
-        let (map, start, end) = parse(input);
-        Some(count_cheats(&map, start, end, 100, 20)).map(|r| r.to_string())
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::days::Day;
-
-    const DAY: super::Day = super::Day;
-    const TEXT: &str = "\
-###############
-#...#...#.....#
-#.#.#.#.#.###.#
-#S#...#.#.#...#
-#######.#.#.###
-#######.#.#...#
-#######.#.###.#
-###..E#...#...#
-###.#######.###
-#...###...#...#
-#.#####.#.###.#
-#.#...#.#.#...#
-#.#.#.#.#.#.###
-#...#...#...###
-###############";
-    #[test]
-    fn part1_example1_full() {
-        assert_eq!(DAY.part1(TEXT), Some(0.to_string()))
-    }
-    #[test]
-    fn part1_example1() {
-        let (map, start, end) = parse(TEXT);
-        assert_eq!(count_cheats(&map, start, end, 64, 2), 1);
-        assert_eq!(count_cheats(&map, start, end, 60, 2), 1);
-        assert_eq!(count_cheats(&map, start, end, 41, 2), 1);
-        assert_eq!(count_cheats(&map, start, end, 40, 2), 2);
-        assert_eq!(count_cheats(&map, start, end, 10, 2), 10);
-    }
-    #[test]
-    fn part1_smallest() {
-        let text = "\
-#####
-#S#E#
-#####";
-        let (map, start, end) = parse(text);
-        assert_eq!(count_cheats(&map, start, end, 100, 2), 1);
-    }
-    #[test]
-    fn part2_example77() {
-        let (map, start, end) = parse(TEXT);
-        assert_eq!(count_cheats(&map, start, end, 77, 20), 0);
-    }
-    #[test]
-    fn part2_example76() {
-        let (map, start, end) = parse(TEXT);
-        assert_eq!(count_cheats(&map, start, end, 76, 20), 3);
-    }
-    #[test]
-    fn part2_example74() {
-        let (map, start, end) = parse(TEXT);
-        assert_eq!(count_cheats(&map, start, end, 74, 20), 3 + 4);
+    fn part1(&self, input: &str) -> Result<Answer, AoCError> {
+        self.part1_with(input, &Params::default())
     }
-    #[test]
-    fn part2_example72() {
-        let (map, start, end) = parse(TEXT);
-        assert_eq!(count_cheats(&map, start, end, 72, 20), 3 + 4 + 22);
+    fn part2(&self, input: &str) -> Result<Answer, AoCError> {
+        self.part2_with(input, &Params::default())
     }
-    #[test]
-    fn part2_example70() {
-        let (map, start, end) = parse(TEXT);
-        assert_eq!(count_cheats(&map, start, end, 70, 20), 3 + 4 + 22 + 12);
+    fn params(&self) -> &'static [Param] {
+        &[THRESHOLD, CHEAT]
     }
-    #[test]
-    fn part2_example68() {
-        let (map, start, end) = parse(TEXT);
-        assert_eq!(count_cheats(&map, start, end, 68, 20), 3 + 4 + 22 + 12 + 14);
+    fn part1_with(&self, input: &str, params: &Params) -> Result<Answer, AoCError> {
+        let (map, start, end) = parse(input)?;
+        let (threshold, cheat) = (params.get(&THRESHOLD, 1)?, params.get(&CHEAT, 1)?);
+        Ok(count_cheats(&map, start, end, threshold, cheat).into())
     }
-    #[test]
-    fn part2_jump_20() {
-        let (map, start, end) = parse(
-            "\
-########
-#S #  E#
-## ##  #
-## ### #
-##     #
-########
-",
-        );
-        assert_eq!(count_cheats(&map, start, end, 1, 2), 1);
-        assert_eq!(count_cheats(&map, start, end, 1, 3), 5);
-        assert_eq!(count_cheats(&map, start, end, 1, 4), 13);
+    fn part2_with(&self, input: &str, params: &Params) -> Result<Answer, AoCError> {
+        let (map, start, end) = parse(input)?;
+        let (threshold, cheat) = (params.get(&THRESHOLD, 2)?, params.get(&CHEAT, 2)?);
+        Ok(count_cheats(&map, start, end, threshold, cheat).into())
     }
 }
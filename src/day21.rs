@@ -1,9 +1,7 @@
-use crate::days;
+use crate::days::{self, AoCError, Answer, Param, Params};
+use crate::memo::Memo;
 use nalgebra::Vector2;
 use num::abs;
-use std::cmp::min;
-use std::collections::HashMap;
-use std::sync::{LazyLock, Mutex};
 
 pub struct Day;
 
@@ -19,6 +17,27 @@ const NUMERIC: [[char; 3]; 4] = [
 ];
 const ARROWS: [[char; 3]; 2] = [[' ', '^', 'A'], ['<', 'v', '>']];
 
+/// The door codes, each with its numeric part.
+fn parse(input: &str) -> Result<Vec<(&str, i64)>, AoCError> {
+    input
+        .lines()
+        .map(|line| {
+            if let Some((i, c)) = line
+                .char_indices()
+                .find(|&(_, c)| c == ' ' || !NUMERIC.iter().any(|row| row.contains(&c)))
+            {
+                return Err(AoCError::parse_at(
+                    input,
+                    &line[i..],
+                    format!("{c} is not on the numeric keypad"),
+                ));
+            }
+            let digits = line.trim_end_matches('A');
+            Ok((line, days::parse(input, digits)?))
+        })
+        .collect()
+}
+
 fn locate<const W: usize, const H: usize>(keypad: &[[char; W]; H], c: char) -> Vec2 {
     for y in 0..keypad.len() {
         for x in 0..keypad[y].len() {
@@ -83,15 +102,12 @@ where
     *results.iter().min().unwrap()
 }
 
-static CACHE: LazyLock<Mutex<HashMap<(i64, String), i64>>> =
-    LazyLock::new(|| Mutex::new(HashMap::new()));
-fn cached_moves_for_keypresses(id: i64, sequence: &str) -> Option<i64> {
-    let cache = CACHE.lock().unwrap();
-    cache.get(&(id, sequence.to_string())).map(|r| *r)
-}
+/// Presses needed for each sequence typed on a keypad, by how many robots there are below it.
+type Presses = Memo<(u32, String), i64>;
 
 fn moves_for_keypresses<F, const W: usize, const H: usize>(
-    id: i64,
+    memo: &Presses,
+    robots: u32,
     keypad: &[[char; W]; H],
     sequence: &str,
     next_moves_for_keypresses: &F,
@@ -99,129 +115,79 @@ fn moves_for_keypresses<F, const W: usize, const H: usize>(
 where
     F: Fn(&str) -> i64,
 {
-    match cached_moves_for_keypresses(id, sequence) {
-        Some(r) => r,
-        None => {
-            let mut result = 0;
-            let mut prev_pos = 'A';
+    memo.get_or_insert_with((robots, sequence.to_string()), || {
+        let mut result = 0;
+        let mut prev_pos = 'A';
 
-            for c in sequence.chars() {
-                result += moves_for_keypress(keypad, prev_pos, c, next_moves_for_keypresses);
+        for c in sequence.chars() {
+            result += moves_for_keypress(keypad, prev_pos, c, next_moves_for_keypresses);
 
-                prev_pos = c;
-            }
+            prev_pos = c;
+        }
 
-            CACHE
-                .lock()
-                .unwrap()
-                .insert((id, sequence.to_string()), result);
+        result
+    })
+}
 
-            result
-        }
+/// Presses needed on your keypad to type `sequence` on a directional keypad with `robots`
+/// robot-operated directional keypads between it and you, counting itself.
+fn arrow_presses(memo: &Presses, robots: u32, sequence: &str) -> i64 {
+    if robots == 0 {
+        return sequence.len() as i64;
     }
+    moves_for_keypresses(memo, robots, &ARROWS, sequence, &|sequence| {
+        arrow_presses(memo, robots - 1, sequence)
+    })
+}
+
+/// The sum of each code's complexity with `robots` directional keypads in front of the door's.
+fn complexity(codes: &[(&str, i64)], robots: u32) -> i64 {
+    let (numeric_memo, arrows_memo) = (Presses::new(), Presses::new());
+    codes
+        .iter()
+        .map(|&(line, numeric)| {
+            let keypresses =
+                moves_for_keypresses(&numeric_memo, robots, &NUMERIC, line, &|sequence| {
+                    arrow_presses(&arrows_memo, robots, sequence)
+                });
+            keypresses * numeric
+        })
+        .sum()
 }
 
+const ROBOTS: Param = Param::per_part(
+    "robots",
+    "2",
+    "25",
+    "robots using directional keypads between you and the door",
+);
+
 impl days::Day for Day {
     fn day(&self) -> u32 {
         21
     }
 
-    fn part1(&self, input: &str) -> Option<String> {
-        Some(
-            input
-                .lines()
-                .map(|line| {
-                    let keypresses = moves_for_keypresses(101, &NUMERIC, line, &|sequence| {
-                        moves_for_keypresses(102, &ARROWS, sequence, &|sequence| {
-                            moves_for_keypresses(103, &ARROWS, sequence, &|sequence: &str| {
-                                sequence.len() as i64
-                            })
-                        })
-                    });
-
-                    let numeric = line[0..min(3, line.len())].parse::<i64>().unwrap();
-
-                    keypresses * numeric
-                })
-                .sum::<i64>(),
-        )
-        .map(|r| r.to_string())
+    fn part1(&self, input: &str) -> Result<Answer, AoCError> {
+        self.part1_with(input, &Params::default())
     }
-    fn part2(&self, input: &str) -> Option<String> {
-        Some(
-            input
-                .lines()
-                .map(|line| {
-                    let keypresses = moves_for_keypresses(1, &NUMERIC, line, &|sequence| {
-                        moves_for_keypresses(2, &ARROWS, sequence, &|sequence| {
-                            moves_for_keypresses(3, &ARROWS, sequence, &|sequence| {
-                                moves_for_keypresses(4, &ARROWS, sequence, &|sequence| {
-                                    moves_for_keypresses(5, &ARROWS, sequence, &|sequence| {
-                                        moves_for_keypresses(6, &ARROWS, sequence, &|sequence| {
-                                            moves_for_keypresses(7, &ARROWS, sequence, &|sequence| {
-                                                moves_for_keypresses(8, &ARROWS, sequence, &|sequence| {
-                                                    moves_for_keypresses(9, &ARROWS, sequence, &|sequence| {
-                                                        moves_for_keypresses(10, &ARROWS, sequence, &|sequence| {
-                                                            moves_for_keypresses(11, &ARROWS, sequence, &|sequence| {
-                                                                moves_for_keypresses(12, &ARROWS, sequence, &|sequence| {
-                                                                    moves_for_keypresses(13, &ARROWS, sequence, &|sequence| {
-                                                                        moves_for_keypresses(14, &ARROWS, sequence, &|sequence| {
-                                                                            moves_for_keypresses(15, &ARROWS, sequence, &|sequence| {
-                                                                                moves_for_keypresses(16, &ARROWS, sequence, &|sequence| {
-                                                                                    moves_for_keypresses(17, &ARROWS, sequence, &|sequence| {
-                                                                                        moves_for_keypresses(18, &ARROWS, sequence, &|sequence| {
-                                                                                            moves_for_keypresses(19, &ARROWS, sequence, &|sequence| {
-                                                                                                moves_for_keypresses(20, &ARROWS, sequence, &|sequence| {
-                                                                                                    moves_for_keypresses(21, &ARROWS, sequence, &|sequence| {
-                                                                                                        moves_for_keypresses(22, &ARROWS, sequence, &|sequence| {
-                                                                                                            moves_for_keypresses(23, &ARROWS, sequence, &|sequence| {
-                                                                                                                moves_for_keypresses(24, &ARROWS, sequence, &|sequence| {
-                                                                                                                    moves_for_keypresses(25, &ARROWS, sequence, &|sequence| {
-                                                                                                                        moves_for_keypresses(26, &ARROWS, sequence, &|sequence: &str| {
-                                                                                                                            sequence.len() as i64
-                                                                                                                        })
-                                                                                                                    })
-                                                                                                                })
-                                                                                                            })
-                                                                                                        })
-                                                                                                    })
-                                                                                                })
-                                                                                            })
-                                                                                        })
-                                                                                    })
-                                                                                })
-                                                                            })
-                                                                        })
-                                                                    })
-                                                                })
-                                                            })
-                                                        })
-                                                    })
-                                                })
-                                            })
-                                        })
-                                    })
-                                })
-                            })
-                        })
-                    });
-
-                    let numeric = line[0..min(3, line.len())].parse::<i64>().unwrap();
-
-                    keypresses * numeric
-                })
-                .sum::<i64>(),
-        )
-            .map(|r| r.to_string())
+    fn part2(&self, input: &str) -> Result<Answer, AoCError> {
+        self.part2_with(input, &Params::default())
+    }
+    fn params(&self) -> &'static [Param] {
+        &[ROBOTS]
+    }
+    fn part1_with(&self, input: &str, params: &Params) -> Result<Answer, AoCError> {
+        Ok(complexity(&parse(input)?, params.get(&ROBOTS, 1)?).into())
+    }
+    fn part2_with(&self, input: &str, params: &Params) -> Result<Answer, AoCError> {
+        Ok(complexity(&parse(input)?, params.get(&ROBOTS, 2)?).into())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::days::Day;
 
-    const DAY: super::Day = super::Day;
     #[test]
     fn moves_for_keypress_a() {
         assert_eq!(
@@ -230,6 +196,7 @@ mod tests {
         );
         assert_eq!(
             moves_for_keypress(&ARROWS, 'A', 'A', &|sequence| moves_for_keypresses(
+                &Memo::new(),
                 1,
                 &ARROWS,
                 sequence,
@@ -246,6 +213,7 @@ mod tests {
         );
         assert_eq!(
             moves_for_keypress(&ARROWS, 'A', '^', &|sequence| moves_for_keypresses(
+                &Memo::new(),
                 1,
                 &ARROWS,
                 sequence,
@@ -254,57 +222,4 @@ mod tests {
             8
         );
     }
-    #[test]
-    fn part1_one_char_3() {
-        let text = "3";
-
-        // ^A
-        // <A >A
-        // v<<A>>^A vA^A
-
-        assert_eq!(DAY.part1(text), Some((12 * 3).to_string()))
-    }
-    #[test]
-    fn part1_example_a() {
-        let text = "029A";
-        assert_eq!(DAY.part1(text), Some((68 * 29).to_string()))
-    }
-    #[test]
-    fn part1_example_b() {
-        let text = "980A";
-        assert_eq!(DAY.part1(text), Some((60 * 980).to_string()))
-    }
-    #[test]
-    fn part1_example_c() {
-        let text = "179A";
-        assert_eq!(DAY.part1(text), Some((68 * 179).to_string()))
-    }
-    #[test]
-    fn part1_example_d() {
-        let text = "456A";
-        assert_eq!(DAY.part1(text), Some((64 * 456).to_string()))
-    }
-    #[test]
-    fn part1_example_e() {
-        let text = "379A";
-        assert_eq!(DAY.part1(text), Some((64 * 379).to_string()))
-    }
-    #[test]
-    fn part1_example1() {
-        let text = "029A
-980A
-179A
-456A
-379A";
-        assert_eq!(DAY.part1(text), Some(126384.to_string()))
-    }
-    #[test]
-    fn part2_example1() {
-        let text = "029A
-980A
-179A
-456A
-379A";
-        assert_eq!(DAY.part2(text), Some("154115708116294".to_string()))
-    }
 }
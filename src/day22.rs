@@ -1,4 +1,4 @@
-use crate::days;
+use crate::days::{self, AoCError, Answer};
 use itertools::Itertools;
 use std::collections::{HashMap, HashSet};
 
@@ -6,6 +6,10 @@ pub struct Day;
 
 impl Day {}
 
+fn parse(input: &str) -> Result<Vec<i64>, AoCError> {
+    input.lines().map(|l| days::parse(input, l)).collect()
+}
+
 fn mix(a: i64, b: i64) -> i64 {
     a ^ b
 }
@@ -31,7 +35,7 @@ fn prices(initial_secret: i64) -> Vec<i64> {
     }
     prices
 }
-fn best_sequence(prices_list: &Vec<Vec<i64>>) -> Vec<i64> {
+fn best_sequence(prices_list: &Vec<Vec<i64>>) -> Option<Vec<i64>> {
     let histogram = &mut HashMap::new();
 
     for prices in prices_list {
@@ -53,10 +57,9 @@ fn best_sequence(prices_list: &Vec<Vec<i64>>) -> Vec<i64> {
     let (result, _) = histogram
         .iter()
         .sorted_by(|(_, a), (_, b)| b.cmp(a))
-        .next()
-        .unwrap();
+        .next()?;
 
-    result.clone()
+    Some(result.clone())
 }
 
 impl days::Day for Day {
@@ -64,29 +67,28 @@ impl days::Day for Day {
         22
     }
 
-    fn part1(&self, input: &str) -> Option<String> {
-        Some(
-            input
-                .lines()
-                .map(|l| l.parse::<i64>().unwrap())
-                .map(|n| {
-                    let mut n = n;
-                    for _ in 0..2000 {
-                        n = next_secret(n);
-                    }
-                    n
-                })
-                .sum::<i64>(),
-        )
-        .map(|r| r.to_string())
+    fn part1(&self, input: &str) -> Result<Answer, AoCError> {
+        Ok(parse(input)?
+            .into_iter()
+            .map(|n| {
+                let mut n = n;
+                for _ in 0..2000 {
+                    n = next_secret(n);
+                }
+                n
+            })
+            .sum::<i64>()
+            .into())
     }
-    fn part2(&self, input: &str) -> Option<String> {
-        let prices_list = input.lines().map(|l| l.parse::<i64>().unwrap())
+    fn part2(&self, input: &str) -> Result<Answer, AoCError> {
+        let prices_list = parse(input)?
+            .into_iter()
             .map(|initial_secret| prices(initial_secret))
             .collect();
 
 
-        let best_sequence = best_sequence(&prices_list);
+        let best_sequence = best_sequence(&prices_list)
+            .ok_or_else(|| AoCError::unsolvable("there are no buyers"))?;
 
         let mut result = 0;
 
@@ -101,39 +103,19 @@ impl days::Day for Day {
             }
         }
 
-        Some(result).map(|r| r.to_string())
+        Ok(result.into())
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::days::Day;
     use super::*;
-    const DAY: super::Day = super::Day;
     #[test]
     fn prices_output() {
         assert_eq!(prices(123)[0..10], vec![3,0,6,5,4,4,6,4,4,2]);
     }
     #[test]
     fn best_sequence_example() {
-        assert_eq!(best_sequence(&vec![prices(123)[0..10].to_vec()]), vec![-1,-1,0,2]);
-    }
-    #[test]
-    fn part1_example1() {
-        let text = "\
-1
-10
-100
-2024";
-        assert_eq!(DAY.part1(text), Some(37327623.to_string()))
-    }
-    #[test]
-    fn part2_example1() {
-        let text = "\
-1
-2
-3
-2024";
-        assert_eq!(DAY.part2(text), Some(23.to_string()))
+        assert_eq!(best_sequence(&vec![prices(123)[0..10].to_vec()]), Some(vec![-1,-1,0,2]));
     }
 }
@@ -1,32 +1,32 @@
-use crate::days;
+use crate::days::{self, AoCError, Answer};
 use std::collections::{HashMap, HashSet};
 
 pub struct Day;
 
 impl Day {}
 
-fn parse(input: &str) -> HashMap<&str, HashSet<&str>> {
+fn parse(input: &str) -> Result<HashMap<&str, HashSet<&str>>, AoCError> {
     let mut result = HashMap::new();
-    input
-        .lines()
-        .map(|line| line.split('-'))
-        .map(|mut pair| (pair.next().unwrap(), pair.next().unwrap()))
-        .for_each(|(l, r)| {
-            result
-                .entry(l)
-                .and_modify(|e: &mut HashSet<&str>| {
-                    e.insert(r);
-                })
-                .or_insert_with(|| HashSet::from_iter([r]));
-            result
-                .entry(r)
-                .and_modify(|e: &mut HashSet<&str>| {
-                    e.insert(l);
-                })
-                .or_insert_with(|| HashSet::from_iter([l]));
-        });
+    for line in input.lines() {
+        let (l, r) = line
+            .split_once('-')
+            .filter(|(l, r)| !l.is_empty() && !r.is_empty())
+            .ok_or_else(|| AoCError::parse_at(input, line, "expected <computer>-<computer>"))?;
+        result
+            .entry(l)
+            .and_modify(|e: &mut HashSet<&str>| {
+                e.insert(r);
+            })
+            .or_insert_with(|| HashSet::from_iter([r]));
+        result
+            .entry(r)
+            .and_modify(|e: &mut HashSet<&str>| {
+                e.insert(l);
+            })
+            .or_insert_with(|| HashSet::from_iter([l]));
+    }
 
-    result
+    Ok(result)
 }
 
 fn intersection<'a>(a: &HashSet<&'a str>, b: &HashSet<&'a str>) -> HashSet<&'a str> {
@@ -80,8 +80,8 @@ impl days::Day for Day {
         23
     }
 
-    fn part1(&self, input: &str) -> Option<String> {
-        let connections = parse(input);
+    fn part1(&self, input: &str) -> Result<Answer, AoCError> {
+        let connections = parse(input)?;
 
         let mut triples = HashSet::new();
 
@@ -97,20 +97,14 @@ impl days::Day for Day {
             }
         }
 
-        Some(
-            triples
-                .iter()
-                .filter(|i| {
-                    i.iter()
-                        .map(|c| c.chars().next().unwrap())
-                        .any(|c| c == 't')
-                })
-                .count(),
-        )
-        .map(|r| r.to_string())
+        Ok(triples
+            .iter()
+            .filter(|i| i.iter().any(|c| c.starts_with('t')))
+            .count()
+            .into())
     }
-    fn part2(&self, input: &str) -> Option<String> {
-        let connections = parse(input);
+    fn part2(&self, input: &str) -> Result<Answer, AoCError> {
+        let connections = parse(input)?;
 
         let mut largest_groups = HashSet::new();
 
@@ -127,90 +121,14 @@ impl days::Day for Day {
             }
         }
 
-        assert_eq!(largest_groups.len(), 1);
+        if largest_groups.len() != 1 {
+            return Err(AoCError::unsolvable(format!(
+                "{} groups share the largest size",
+                largest_groups.len()
+            )));
+        }
         let largest_set = largest_groups.iter().next().unwrap();
 
-        Some(largest_set.join(","))
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::days::Day;
-
-    const DAY: super::Day = super::Day;
-    #[test]
-    fn part1_example1() {
-        let text = "\
-kh-tc
-qp-kh
-de-cg
-ka-co
-yn-aq
-qp-ub
-cg-tb
-vc-aq
-tb-ka
-wh-tc
-yn-cg
-kh-ub
-ta-co
-de-co
-tc-td
-tb-wq
-wh-td
-ta-ka
-td-qp
-aq-cg
-wq-ub
-ub-vc
-de-ta
-wq-aq
-wq-vc
-wh-yn
-ka-de
-kh-ta
-co-tc
-wh-qp
-tb-vc
-td-yn";
-        assert_eq!(DAY.part1(text), Some(7.to_string()))
-    }
-    #[test]
-    fn part2_example1() {
-        let text = "\
-kh-tc
-qp-kh
-de-cg
-ka-co
-yn-aq
-qp-ub
-cg-tb
-vc-aq
-tb-ka
-wh-tc
-yn-cg
-kh-ub
-ta-co
-de-co
-tc-td
-tb-wq
-wh-td
-ta-ka
-td-qp
-aq-cg
-wq-ub
-ub-vc
-de-ta
-wq-aq
-wq-vc
-wh-yn
-ka-de
-kh-ta
-co-tc
-wh-qp
-tb-vc
-td-yn";
-        assert_eq!(DAY.part2(text), Some("co,de,ka,ta".to_string()))
+        Ok(Answer::list(largest_set.iter().copied()))
     }
 }
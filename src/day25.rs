@@ -1,65 +1,62 @@
 use std::cmp::max;
-use crate::days;
+use crate::days::{self, AoCError, Answer};
+use crate::parsing;
 
 pub struct Day;
 
 impl Day {}
 
-fn parse(input: &str) -> (Vec<Vec<usize>>, Vec<Vec<usize>>) {
+/// The height of each column, for each lock or key.
+type Heights = Vec<Vec<usize>>;
+
+/// The column heights of the locks and of the keys, counted from the row of all `#` at their
+/// top and bottom respectively.
+fn parse(input: &str) -> Result<(Heights, Heights), AoCError> {
     let mut locks = Vec::new();
     let mut keys = Vec::new();
 
-    input.split_terminator("\n\n")
-        .for_each(|schematic| {
-            let mut lines = schematic.lines();
-            if lines.next().unwrap() == "#####" {
-                let lock = lines.enumerate()
-                    .fold(vec![0usize;5], |acc, (i, line)| {
-                        line.chars()
-                            .map(|c| match c {
-                                '#' => i+1,
-                                _ => 0
-                            })
-                            .zip(acc)
-                            .map(|(a,b)| max(a,b))
-                            .collect::<Vec<_>>()
-                    });
-                locks.push(lock);
-            } else {
-                let key = lines.enumerate()
-                    .fold(vec![0usize;5], |acc, (i, line)| {
-                        line.chars()
-                            .map(|c| match c {
-                                '#' => 5-i,
-                                _ => 0
-                            })
-                            .zip(acc)
-                            .map(|(a,b)| max(a,b))
-                            .collect::<Vec<_>>()
-                    });
-                keys.push(key);
-            }
-        });
-        (locks, keys)
+    for schematic in parsing::sections(input) {
+        let filled = parsing::grid(input, schematic, |c| match c {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            _ => Err(format!("{c} is not # or .")),
+        })?;
+        let is_lock = (0..filled.width()).all(|x| filled[(x, 0)]);
+        let heights = (0..filled.width())
+            .map(|x| (1..filled.height())
+                .filter(|&y| filled[(x, y)])
+                .map(|y| if is_lock { y } else { filled.height() - 1 - y })
+                .fold(0, max))
+            .collect::<Vec<_>>();
+        if is_lock {
+            locks.push(heights);
+        } else {
+            keys.push(heights);
+        }
+    }
+    Ok((locks, keys))
 }
 
 impl days::Day for Day {
     fn day(&self) -> u32 {
         25
     }
+    fn parts(&self) -> &'static [u32] {
+        &[1]
+    }
 
-    fn part1(&self, input: &str) -> Option<String> {
-        let (locks, keys) = parse(input);
+    fn part1(&self, input: &str) -> Result<Answer, AoCError> {
+        let (locks, keys) = parse(input)?;
 
-        Some(keys.iter()
+        Ok(keys.iter()
             .map(|key| locks.iter()
                 .filter(|lock| lock.iter().zip(key.iter()).all(|(l,k)| k + l <= 5))
                 .count())
-            .sum::<usize>())
-            .map(|r| r.to_string())
+            .sum::<usize>()
+            .into())
     }
-    fn part2(&self, _input: &str) -> Option<String> {
-        None
+    fn part2(&self, _input: &str) -> Result<Answer, AoCError> {
+        Err(AoCError::unsolvable("day 25 has no part 2"))
     }
 }
 
@@ -69,7 +66,6 @@ mod tests {
     use super::*;
 
     const DAY: super::Day = super::Day;
-
     #[test]
     fn parse_lock() {
         let text = "\
@@ -112,7 +108,7 @@ mod tests {
 #.#..
 #.#.#
 #####";
-        let (locks, _) = parse(text);
+        let (locks, _) = parse(text).unwrap();
         assert_eq!(locks, vec![vec![0,5,3,4,3], vec![1,2,0,5,3]])
     }
 
@@ -158,56 +154,12 @@ mod tests {
 #.#..
 #.#.#
 #####";
-        let (_, keys) = parse(text);
+        let (_, keys) = parse(text).unwrap();
         assert_eq!(keys, vec![vec![5,0,2,1,3], vec![4,3,4,0,2], vec![3,0,2,0,1]])
     }
     #[test]
-    fn part1_example1() {
-        let text = "\
-#####
-.####
-.####
-.####
-.#.#.
-.#...
-.....
-
-#####
-##.##
-.#.##
-...##
-...#.
-...#.
-.....
-
-.....
-#....
-#....
-#...#
-#.#.#
-#.###
-#####
-
-.....
-.....
-#.#..
-###..
-###.#
-###.#
-#####
-
-.....
-.....
-.....
-#....
-#.#..
-#.#.#
-#####";
-        assert_eq!(DAY.part1(text), Some(3.to_string()))
-    }
-    #[test]
     fn part2_example1() {
         let text = "";
-        assert_eq!(DAY.part2(text), None)
+        assert!(DAY.part2(text).is_err())
     }
 }
@@ -1,4 +1,4 @@
-use crate::days;
+use crate::days::{self, AoCError, Answer};
 use regex::Regex;
 
 pub struct Day;
@@ -10,20 +10,16 @@ impl days::Day for Day {
         3
     }
 
-    fn part1(&self, input: &str) -> Option<String> {
+    fn part1(&self, input: &str) -> Result<Answer, AoCError> {
         let mul_pattern = Regex::new(r"mul\((\d+),(\d+)\)").unwrap();
 
-        Some(
-            mul_pattern
-                .captures_iter(input)
-                .map(|c| c.extract())
-                .map(|(_, [left, right])| {
-                    left.parse::<i64>().unwrap() * right.parse::<i64>().unwrap()
-                })
-                .sum::<i64>(),
-        ).map(|r| r.to_string())
+        let mut sum = 0i64;
+        for (_, [left, right]) in mul_pattern.captures_iter(input).map(|c| c.extract()) {
+            sum += days::parse::<i64>(input, left)? * days::parse::<i64>(input, right)?;
+        }
+        Ok(sum.into())
     }
-    fn part2(&self, input: &str) -> Option<String> {
+    fn part2(&self, input: &str) -> Result<Answer, AoCError> {
         let mul_pattern =
             Regex::new(r"(?<op2>mul)\((?<p1>\d+),(?<p2>\d+)\)|(?<op0>do|don't)\(\)").unwrap();
 
@@ -55,23 +51,6 @@ impl days::Day for Day {
                 }
             }
         }
-        Some(sum).map(|r| r.to_string())
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::days::Day;
-
-    const DAY: crate::day3::Day = crate::day3::Day;
-    #[test]
-    fn part1_example1() {
-        let text = "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
-        assert_eq!(DAY.part1(text), Some("161".to_string()))
-    }
-    #[test]
-    fn part2_example1() {
-        let text = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
-        assert_eq!(DAY.part2(text), Some("48".to_string()))
+        Ok(sum.into())
     }
 }
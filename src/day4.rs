@@ -1,4 +1,6 @@
-use crate::days;
+use crate::compass::Dir8;
+use crate::days::{self, AoCError, Answer};
+use crate::grid::{Grid, Pos};
 
 pub struct Day;
 
@@ -9,58 +11,26 @@ impl days::Day for Day {
         4
     }
 
-    fn part1(&self, input: &str) -> Option<String> {
-        let puzzle = {
-            let mut puzzle = vec![];
+    fn part1(&self, input: &str) -> Result<Answer, AoCError> {
+        let puzzle = Grid::chars(input)?;
 
-            for line in input.split_terminator("\n") {
-                let mut chars = vec![];
-                for c in line.chars() {
-                    chars.push(c);
-                }
-                puzzle.push(chars);
-            }
-            puzzle
-        };
-
-        const DIRECTIONS: [(i32, i32); 8] = [
-            (1, 0),
-            (1, 1),
-            (0, 1),
-            (-1, 1),
-            (-1, 0),
-            (-1, -1),
-            (0, -1),
-            (1, -1),
-        ];
         const XMAS: [char; 4] = ['X', 'M', 'A', 'S'];
 
-        fn is_xmas(puzzle: &Vec<Vec<char>>, pos: (usize, usize), dir: (i32, i32)) -> bool {
-            if puzzle[pos.1][pos.0] != 'X' {
-                return false;
-            }
-            let ((x, y), (dx, dy)) = (pos, dir);
-            let mut x = x as i32;
-            let mut y = y as i32;
+        fn is_xmas(puzzle: &Grid<char>, pos: Pos, dir: Dir8) -> bool {
+            let mut pos = Some(pos);
             for c in XMAS {
-                if x < 0 || y < 0 || puzzle
-                    .get(y as usize)
-                    .and_then(|l| l.get(x as usize))
-                    .map(|&puzzle_c| puzzle_c != c)
-                    .unwrap_or(true)
-                {
-                    return false;
+                match pos {
+                    Some(p) if puzzle[p] == c => pos = puzzle.step(p, dir.offset()),
+                    _ => return false,
                 }
-                x += dx;
-                y += dy;
             }
 
             return true;
         }
 
-        fn count_xmas(puzzle: &Vec<Vec<char>>, pos: (usize, usize)) -> i64 {
+        fn count_xmas(puzzle: &Grid<char>, pos: Pos) -> i64 {
             let mut count = 0;
-            for dir in DIRECTIONS {
+            for dir in Dir8::ALL {
                 if is_xmas(puzzle, pos, dir) {
                     count += 1;
                 }
@@ -69,83 +39,35 @@ impl days::Day for Day {
         }
 
         let mut result = 0;
-        for y in 0..puzzle.len() {
-            for x in 0..puzzle[y].len() {
-                result += count_xmas(&puzzle, (x, y));
-            }
+        for pos in puzzle.positions() {
+            result += count_xmas(&puzzle, pos);
         }
 
-        Some(result).map(|r| r.to_string())
+        Ok(result.into())
     }
-    fn part2(&self, input: &str) -> Option<String> {
-        let puzzle = {
-            let mut puzzle = vec![];
+    fn part2(&self, input: &str) -> Result<Answer, AoCError> {
+        let puzzle = Grid::chars(input)?;
 
-            for line in input.split_terminator("\n") {
-                let mut chars = vec![];
-                for c in line.chars() {
-                    chars.push(c);
-                }
-                puzzle.push(chars);
-            }
-            puzzle
-        };
-
-        fn is_xmas(puzzle: &Vec<Vec<char>>, pos: (usize, usize)) -> bool {
+        fn is_xmas(puzzle: &Grid<char>, pos: Pos) -> bool {
             let (x,y) = pos;
             const MS:[char;2] = ['M','S'];
 
-            puzzle[y][x] == 'A'
-                && MS.contains(&puzzle[y-1][x-1]) && MS.contains(&puzzle[y+1][x+1])
-                && puzzle[y-1][x-1] != puzzle[y+1][x+1]
-                && MS.contains(&puzzle[y-1][x+1]) && MS.contains(&puzzle[y+1][x-1])
-                && puzzle[y-1][x+1] != puzzle[y+1][x-1]
+            puzzle[(x, y)] == 'A'
+                && MS.contains(&puzzle[(x-1, y-1)]) && MS.contains(&puzzle[(x+1, y+1)])
+                && puzzle[(x-1, y-1)] != puzzle[(x+1, y+1)]
+                && MS.contains(&puzzle[(x+1, y-1)]) && MS.contains(&puzzle[(x-1, y+1)])
+                && puzzle[(x+1, y-1)] != puzzle[(x-1, y+1)]
         }
 
         let mut result = 0;
-        for y in 1..puzzle.len() - 1 {
-            for x in 1..puzzle[y].len() - 1 {
+        for y in 1..puzzle.height().saturating_sub(1) {
+            for x in 1..puzzle.width().saturating_sub(1) {
                 if is_xmas(&puzzle,(x,y)) {
                     result += 1;
                 }
             }
         }
 
-        Some(result).map(|r| r.to_string())
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::days::Day;
-
-    const DAY: super::Day = super::Day;
-    #[test]
-    fn part1_example1() {
-        let text = "MMMSXXMASM
-MSAMXMSMSA
-AMXSXMAAMM
-MSAMASMSMX
-XMASAMXAMM
-XXAMMXXAMA
-SMSMSASXSS
-SAXAMASAAA
-MAMMMXMMMM
-MXMXAXMASX";
-        assert_eq!(DAY.part1(text), Some("18".to_string()))
-    }
-    #[test]
-    fn part2_example1() {
-        let text = "MMMSXXMASM
-MSAMXMSMSA
-AMXSXMAAMM
-MSAMASMSMX
-XMASAMXAMM
-XXAMMXXAMA
-SMSMSASXSS
-SAXAMASAAA
-MAMMMXMMMM
-MXMXAXMASX";
-        assert_eq!(DAY.part2(text), Some("9".to_string()))
+        Ok(result.into())
     }
 }
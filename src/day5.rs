@@ -1,4 +1,5 @@
-use crate::days;
+use crate::days::{self, AoCError, Answer};
+use crate::parsing;
 use std::cmp::Ordering;
 use std::collections::HashMap;
 
@@ -6,32 +7,26 @@ pub struct Day;
 
 impl Day {}
 
-fn parse(input: &str) -> (HashMap<i64, Vec<i64>>, Vec<Vec<i64>>) {
-    let mut i = input.split("\n\n").map(|part| part.split_terminator('\n'));
+type Rules = HashMap<i64, Vec<i64>>;
 
-    let rules = i
-        .next()
-        .unwrap()
-        .map(|rule| {
-            let mut rule = rule.split('|');
-            let first = rule.next().and_then(|n| n.parse::<i64>().ok()).unwrap();
-            let second = rule.next().and_then(|n| n.parse::<i64>().ok()).unwrap();
+fn parse(input: &str) -> Result<(Rules, Vec<Vec<i64>>), AoCError> {
+    let (rules, prints) = parsing::two_sections(input, "rules, a blank line, then updates")?;
 
-            (first, second)
-        })
-        .fold(HashMap::new(), |mut map, (first, second)| {
-            map.entry(first).or_insert_with(Vec::new).push(second);
-            map
-        });
+    let mut rule_map = HashMap::new();
+    for rule in rules.lines() {
+        let [first, second] = parsing::fields(input, rule, "{before}|{after}")?;
+        rule_map
+            .entry(days::parse::<i64>(input, first)?)
+            .or_insert_with(Vec::new)
+            .push(days::parse(input, second)?);
+    }
 
-    let prints = i.next().unwrap().map(|print| {
-        print
-            .split(",")
-            .map(|n| n.parse().unwrap())
-            .collect::<Vec<i64>>()
-    });
+    let prints = prints
+        .lines()
+        .map(|print| parsing::separated(input, print, ","))
+        .collect::<Result<Vec<Vec<i64>>, AoCError>>()?;
 
-    (rules, prints.collect())
+    Ok((rule_map, prints))
 }
 
 fn is_ordered(rules: &HashMap<i64, Vec<i64>>, print: &Vec<i64>) -> bool {
@@ -55,21 +50,22 @@ impl days::Day for Day {
         5
     }
 
-    fn part1(&self, input: &str) -> Option<String> {
-        let (rules, prints) = parse(input);
+    fn part1(&self, input: &str) -> Result<Answer, AoCError> {
+        let (rules, prints) = parse(input)?;
 
-        Some(
+        Ok(
             prints
                 .iter()
                 .filter(|&print| is_ordered(&rules, print))
                 .map(|print| print[print.len() / 2])
-                .sum::<i64>(),
-        ).map(|r| r.to_string())
+                .sum::<i64>()
+                .into(),
+        )
     }
-    fn part2(&self, input: &str) -> Option<String> {
-        let (rules, prints) = parse(input);
+    fn part2(&self, input: &str) -> Result<Answer, AoCError> {
+        let (rules, prints) = parse(input)?;
 
-        Some(
+        Ok(
             prints
                 .iter()
                 .filter(|&print| !is_ordered(&rules, print))
@@ -89,78 +85,8 @@ impl days::Day for Day {
                     print
                 })
                 .map(|print| print[print.len() / 2])
-                .sum::<i64>(),
-        ).map(|r| r.to_string())
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::days::Day;
-
-    const DAY: super::Day = super::Day;
-    #[test]
-    fn part1_example1() {
-        let text = "47|53
-97|13
-97|61
-97|47
-75|29
-61|13
-75|53
-29|13
-97|29
-53|29
-61|53
-97|53
-61|29
-47|13
-75|47
-97|75
-47|61
-75|61
-47|29
-75|13
-53|13
-
-75,47,61,53,29
-97,61,53,29,13
-75,29,13
-75,97,47,61,53
-61,13,29
-97,13,75,29,47";
-        assert_eq!(DAY.part1(text), Some("143".to_string()))
-    }
-    #[test]
-    fn part2_example1() {
-        let text = "47|53
-97|13
-97|61
-97|47
-75|29
-61|13
-75|53
-29|13
-97|29
-53|29
-61|53
-97|53
-61|29
-47|13
-75|47
-97|75
-47|61
-75|61
-47|29
-75|13
-53|13
-
-75,47,61,53,29
-97,61,53,29,13
-75,29,13
-75,97,47,61,53
-61,13,29
-97,13,75,29,47";
-        assert_eq!(DAY.part2(text), Some("123".to_string()))
+                .sum::<i64>()
+                .into(),
+        )
     }
 }
@@ -1,22 +1,23 @@
-use crate::days;
+use crate::days::{self, AoCError, Answer};
+use crate::parsing;
 
 pub struct Day;
 
 impl Day {}
 
-fn parse(input: &str) -> Vec<(i64, Vec<i64>)> {
+fn parse(input: &str) -> Result<Vec<(i64, Vec<i64>)>, AoCError> {
     input
-        .split_terminator('\n')
+        .lines()
         .map(|line| {
-            let mut parts = line.split(": ");
-            let total = parts.next().unwrap().parse().unwrap();
-            let items = parts
-                .next()
-                .unwrap()
+            let [total, items] = parsing::fields(input, line, "{total}: {items}")?;
+            let items = items
                 .split_whitespace()
-                .map(|i| i.parse().unwrap())
-                .collect();
-            return (total, items);
+                .map(|i| days::parse(input, i))
+                .collect::<Result<Vec<i64>, AoCError>>()?;
+            if items.is_empty() {
+                return Err(AoCError::parse_at(input, line, "equation has no items"));
+            }
+            Ok((days::parse(input, total)?, items))
         })
         .collect()
 }
@@ -64,63 +65,28 @@ impl days::Day for Day {
         7
     }
 
-    fn part1(&self, input: &str) -> Option<String> {
-        let equations = parse(input);
+    fn part1(&self, input: &str) -> Result<Answer, AoCError> {
+        let equations = parse(input)?;
 
-        Some(
+        Ok(
             equations
                 .iter()
                 .filter(|(total, values)| is_possible(*total, values, &OP.to_vec()))
                 .map(|(total, _)| total)
-                .sum::<i64>(),
-        ).map(|r| r.to_string())
+                .sum::<i64>()
+                .into(),
+        )
     }
-    fn part2(&self, input: &str) -> Option<String> {
-        let equations = parse(input);
+    fn part2(&self, input: &str) -> Result<Answer, AoCError> {
+        let equations = parse(input)?;
 
-        Some(
+        Ok(
             equations
                 .iter()
                 .filter(|(total, values)| is_possible(*total, values, &OP2.to_vec()))
                 .map(|(total, _)| total)
-                .sum::<i64>(),
-        ).map(|r| r.to_string())
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::days::Day;
-
-    const DAY: super::Day = super::Day;
-    #[test]
-    fn part1_example1() {
-        let text = "\
-190: 10 19
-3267: 81 40 27
-83: 17 5
-156: 15 6
-7290: 6 8 6 15
-161011: 16 10 13
-192: 17 8 14
-21037: 9 7 18 13
-292: 11 6 16 20\
-";
-        assert_eq!(DAY.part1(text), Some("3749".to_string()))
-    }
-    #[test]
-    fn part2_example1() {
-        let text = "\
-190: 10 19
-3267: 81 40 27
-83: 17 5
-156: 15 6
-7290: 6 8 6 15
-161011: 16 10 13
-192: 17 8 14
-21037: 9 7 18 13
-292: 11 6 16 20\
-";
-        assert_eq!(DAY.part2(text), Some("11387".to_string()))
+                .sum::<i64>()
+                .into(),
+        )
     }
 }
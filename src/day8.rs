@@ -1,4 +1,5 @@
-use crate::days;
+use crate::days::{self, AoCError, Answer};
+use crate::grid::Grid;
 use std::collections::{HashMap, HashSet};
 use nalgebra::Vector2;
 
@@ -6,23 +7,28 @@ pub struct Day;
 
 impl Day {}
 
+/// The positions of the antennae of each frequency.
+type Antennae = HashMap<char, Vec<Vector2<i64>>>;
+
+fn parse(input: &str) -> Result<(Grid<char>, Antennae), AoCError> {
+    let map = Grid::chars(input)?;
+    let freq_antennae = map
+        .iter()
+        .filter(|(_, c)| **c != '.')
+        .fold(HashMap::new(), |mut m, ((x, y), &c)| {
+            m.entry(c).or_insert_with(|| vec![]).push(Vector2::new(x as i64, y as i64));
+            m
+        });
+    Ok((map, freq_antennae))
+}
+
 impl days::Day for Day {
     fn day(&self) -> u32 {
         8
     }
 
-    fn part1(&self, input: &str) -> Option<String> {
-        let freq_antennae = input.split_terminator('\n').enumerate()
-            .flat_map(|(y, line)| line.chars().enumerate()
-                .filter(|(_, c)| *c != '.')
-                .map(move |(x, c)| ((x, y), c)))
-            .fold(HashMap::new(), |mut m, ((x, y), c)| {
-                m.entry(c).or_insert_with(|| vec![]).push(Vector2::new(x as i64, y as i64));
-                m
-            });
-
-        let width = input.split_terminator('\n').next().unwrap().len() as i64;
-        let height = input.split_terminator('\n').count() as i64;
+    fn part1(&self, input: &str) -> Result<Answer, AoCError> {
+        let (map, freq_antennae) = parse(input)?;
 
         let mut antinodes = HashSet::new();
 
@@ -32,11 +38,11 @@ impl days::Day for Day {
                     if a != b {
                         let diff = b - a;
                         let antinode = b + &diff;
-                        if antinode.x >=0 && antinode.y >=0 && antinode.x < width && antinode.y < height {
+                        if map.contains(antinode.x, antinode.y) {
                             antinodes.insert(antinode);
                         }
                         let antinode = a - &diff;
-                        if antinode.x >=0 && antinode.y >=0 && antinode.x < width && antinode.y < height {
+                        if map.contains(antinode.x, antinode.y) {
                             antinodes.insert(antinode);
                         }
                     }
@@ -44,20 +50,10 @@ impl days::Day for Day {
             }
         }
 
-        Some(antinodes.len() as i64).map(|r| r.to_string())
+        Ok(antinodes.len().into())
     }
-    fn part2(&self, input: &str) -> Option<String> {
-        let freq_antennae = input.split_terminator('\n').enumerate()
-            .flat_map(|(y, line)| line.chars().enumerate()
-                .filter(|(_, c)| *c != '.')
-                .map(move |(x, c)| ((x, y), c)))
-            .fold(HashMap::new(), |mut m, ((x, y), c)| {
-                m.entry(c).or_insert_with(|| vec![]).push(Vector2::new(x as i64, y as i64));
-                m
-            });
-
-        let width = input.split_terminator('\n').next().unwrap().len() as i64;
-        let height = input.split_terminator('\n').count() as i64;
+    fn part2(&self, input: &str) -> Result<Answer, AoCError> {
+        let (map, freq_antennae) = parse(input)?;
 
         let mut antinodes = HashSet::new();
 
@@ -68,14 +64,14 @@ impl days::Day for Day {
                         let diff = b - a;
 
                         let mut antinode = *a;
-                        while antinode.x >=0 && antinode.y >=0 && antinode.x < width && antinode.y < height {
+                        while map.contains(antinode.x, antinode.y) {
                             antinodes.insert(antinode);
 
                             antinode = &antinode - &diff;
                         }
 
                         let mut antinode = *b;
-                        while antinode.x >=0 && antinode.y >=0 && antinode.x < width && antinode.y < height {
+                        while map.contains(antinode.x, antinode.y) {
                             antinodes.insert(antinode);
 
                             antinode = &antinode + &diff;
@@ -85,118 +81,6 @@ impl days::Day for Day {
             }
         }
 
-        Some(antinodes.len() as i64).map(|r| r.to_string())
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::days::Day;
-
-    const DAY: super::Day = super::Day;
-    #[test]
-    fn part1_no_nodes() {
-        let text = "\
-............
-............
-............
-............
-............
-............
-............
-............
-............
-............
-............
-............
-";
-        assert_eq!(DAY.part1(text), Some("0".to_string()))
-    }
-    #[test]
-    fn part1_one_node() {
-        let text = "\
-............
-............
-............
-............
-............
-......A.....
-............
-............
-............
-............
-............
-............
-";
-        assert_eq!(DAY.part1(text), Some("0".to_string()))
-    }
-    #[test]
-    fn part1_two_nodes() {
-        let text = "\
-............
-............
-............
-............
-............
-......A.....
-............
-.......A....
-............
-............
-............
-............
-";
-        assert_eq!(DAY.part1(text), Some("2".to_string()))
-    }
-    #[test]
-    fn part1_example1() {
-        let text = "\
-............
-........0...
-.....0......
-.......0....
-....0.......
-......A.....
-............
-............
-........A...
-.........A..
-............
-............
-";
-        assert_eq!(DAY.part1(text), Some("14".to_string()))
-    }
-    #[test]
-    fn part2_example1() {
-        let text = "\
-T.........
-...T......
-.T........
-..........
-..........
-..........
-..........
-..........
-..........
-..........";
-        assert_eq!(DAY.part2(text), Some("9".to_string()))
-    }
-    #[test]
-    fn part2_example2() {
-        let text = "\
-............
-........0...
-.....0......
-.......0....
-....0.......
-......A.....
-............
-............
-........A...
-.........A..
-............
-............
-";
-        assert_eq!(DAY.part2(text), Some("34".to_string()))
+        Ok(antinodes.len().into())
     }
 }
@@ -1,18 +1,24 @@
-use crate::days;
+use crate::days::{self, AoCError, Answer};
 use itertools::Itertools;
 use std::cmp::{max, min};
 
 pub struct Day;
 
-// Not: 6434671719741 -- too high
-
 impl Day {}
 
 fn value(c: char) -> i32 {
     c as i32 - '0' as i32
 }
-fn parse(input: &str) -> Vec<i32> {
-    input
+fn parse(input: &str) -> Result<Vec<i32>, AoCError> {
+    if let Some((i, c)) = input.char_indices().find(|(_, c)| !c.is_ascii_digit()) {
+        return Err(AoCError::parse_at(
+            input,
+            &input[i..],
+            format!("{c} is not a digit"),
+        ));
+    }
+
+    Ok(input
         .chars()
         .chunks(2)
         .into_iter()
@@ -29,7 +35,7 @@ fn parse(input: &str) -> Vec<i32> {
             }
             result
         })
-        .collect()
+        .collect())
 }
 
 fn is_big_enough_gap(map: &Vec<i32>, target: usize, size: usize) -> Option<usize> {
@@ -91,8 +97,8 @@ impl days::Day for Day {
         9
     }
 
-    fn part1(&self, input: &str) -> Option<String> {
-        let mut map = parse(input);
+    fn part1(&self, input: &str) -> Result<Answer, AoCError> {
+        let mut map = parse(input)?;
 
         let mut i = map.len() - 1;
         let mut target = 0;
@@ -110,44 +116,31 @@ impl days::Day for Day {
             i -= 1;
         }
 
-        Some(
+        Ok(
             map.iter()
                 .enumerate()
                 .map(|(i, id)| i as i64 * (max(*id as i64, 0)))
-                .sum::<i64>(),
+                .sum::<i64>()
+                .into(),
         )
-        .map(|r| r.to_string())
     }
-    fn part2(&self, input: &str) -> Option<String> {
-        let mut map = parse(input);
+    fn part2(&self, input: &str) -> Result<Answer, AoCError> {
+        let mut map = parse(input)?;
 
         defrag(&mut map);
 
-        Some(
+        Ok(
             map.iter()
                 .enumerate()
                 .map(|(i, id)| i as i64 * (max(*id as i64, 0)))
                 .sum::<i64>()
-        ).map(|r| r.to_string())
+                .into(),
+        )
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::days::Day;
-
-    const DAY: super::Day = super::Day;
-    #[test]
-    fn part1_example1() {
-        let text = "2333133121414131402";
-        assert_eq!(DAY.part1(text), Some("1928".to_string()))
-    }
-    #[test]
-    fn part2_example1() {
-        let text = "2333133121414131402";
-        assert_eq!(DAY.part2(text), Some("2858".to_string()))
-    }
-
     #[test]
     fn defrag() {
         let mut map = vec![0, 0, -1, -1, 1];
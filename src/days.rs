@@ -1,21 +1,273 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
 use thiserror::Error;
 
+pub use crate::answer::Answer;
+
 #[derive(Error, Debug)]
 pub enum AoCError {
     #[error("an IO error occurred")]
     Io(#[from] std::io::Error),
     #[error("an HTTP error occurred")]
     Http(#[from] reqwest::Error),
-    #[error("could not find .cookie file containing session like session=<value>")]
+    #[error("{0} returned HTTP status {1}")]
+    HttpStatus(String, u16),
+    #[error("no session: set AOC_SESSION or create a .cookie file containing session=<value>")]
     CookieFile(std::io::Error),
+    #[error("could not parse config file {0}: {1}")]
+    ConfigFile(String, toml::de::Error),
+    #[error("invalid configuration: {0}")]
+    Config(String),
+    #[error("could not parse answers file {0}: {1}")]
+    AnswerFile(String, toml::de::Error),
+    #[error("not submitting: {0}")]
+    Submission(String),
+    #[error("invalid day registry: {0}")]
+    Registry(String),
+    #[error("cannot create day: {0}")]
+    NewDay(String),
+    #[error("invalid example {0}: {1}")]
+    Example(String, String),
+    #[error("line {line}, column {column}: {message}")]
+    Parse {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    #[error("no solution: {0}")]
+    Unsolvable(String),
+    #[error("invalid parameter {0}: {1}")]
+    InvalidParameter(String, String),
+    #[error("solver panicked: {0}")]
+    Panicked(String),
+    #[error("timed out after {0:?}")]
+    Timeout(std::time::Duration),
+    #[error("cancelled")]
+    Cancelled,
+}
+
+impl AoCError {
+    /// A parse error at `at`, which should be a slice of `input`, located by 1-based line and
+    /// column. Anything else is reported at the start of the input.
+    pub fn parse_at(input: &str, at: &str, message: impl Into<String>) -> AoCError {
+        let offset = (at.as_ptr() as usize)
+            .checked_sub(input.as_ptr() as usize)
+            .filter(|&offset| input.is_char_boundary(offset))
+            .unwrap_or(0);
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+
+        AoCError::Parse {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            message: message.into(),
+        }
+    }
+
+    pub fn unsolvable(message: impl Into<String>) -> AoCError {
+        AoCError::Unsolvable(message.into())
+    }
+}
+
+/// Parses `token`, a slice of `input`, reporting where it was on failure.
+pub fn parse<T: FromStr>(input: &str, token: &str) -> Result<T, AoCError> {
+    token.parse().map_err(|_| {
+        AoCError::parse_at(
+            input,
+            token,
+            format!("'{token}' is not a valid {}", std::any::type_name::<T>()),
+        )
+    })
+}
+
+/// Runs a solver, turning a panic into an error so that one broken part does not stop the rest.
+pub fn catch_panic<F>(solve: F) -> Result<Answer, AoCError>
+where
+    F: FnOnce() -> Result<Answer, AoCError>,
+{
+    std::panic::catch_unwind(std::panic::AssertUnwindSafe(solve)).unwrap_or_else(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_string());
+        Err(AoCError::Panicked(message))
+    })
+}
+
+/// A value the puzzle text gives rather than the input, which the examples often change.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Param {
+    pub name: &'static str,
+    /// The values for the real input in parts 1 and 2
+    pub defaults: [&'static str; 2],
+    pub help: &'static str,
 }
 
-pub trait Day {
+impl Param {
+    pub const fn new(name: &'static str, default: &'static str, help: &'static str) -> Param {
+        Param {
+            name,
+            defaults: [default, default],
+            help,
+        }
+    }
+
+    pub const fn per_part(
+        name: &'static str,
+        part1: &'static str,
+        part2: &'static str,
+        help: &'static str,
+    ) -> Param {
+        Param {
+            name,
+            defaults: [part1, part2],
+            help,
+        }
+    }
+}
+
+/// Values given for parameters by name, in place of their defaults.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Params(BTreeMap<String, String>);
+
+impl Params {
+    pub fn set(mut self, name: &str, value: &str) -> Params {
+        self.0.insert(name.to_string(), value.to_string());
+        self
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.0.keys().map(|name| name.as_str())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The value of `param` in `part`: the one given, or else its default.
+    pub fn get<T: FromStr>(&self, param: &Param, part: u32) -> Result<T, AoCError> {
+        let value = match self.0.get(param.name) {
+            Some(value) => value.as_str(),
+            None => param.defaults[if part == 1 { 0 } else { 1 }],
+        };
+        value.parse().map_err(|_| {
+            AoCError::InvalidParameter(
+                param.name.to_string(),
+                format!("'{value}' is not a valid {}", std::any::type_name::<T>()),
+            )
+        })
+    }
+}
+
+/// As they would be given with `--param`, e.g. `bytes=12 size=6`.
+impl Display for Params {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let params: Vec<_> = self
+            .iter()
+            .map(|(name, value)| format!("{name}={value}"))
+            .collect();
+        write!(f, "{}", params.join(" "))
+    }
+}
+
+impl FromIterator<(String, String)> for Params {
+    fn from_iter<I: IntoIterator<Item = (String, String)>>(iter: I) -> Params {
+        Params(iter.into_iter().collect())
+    }
+}
+
+pub trait Day: Sync {
     fn day(&self) -> u32;
-    fn part1(&self, _lines: &str) -> Option<String> {
-        None
+    fn parts(&self) -> &'static [u32] {
+        &[1, 2]
+    }
+    fn part1(&self, _lines: &str) -> Result<Answer, AoCError> {
+        Err(AoCError::unsolvable("part 1 is not implemented"))
+    }
+    fn part2(&self, _lines: &str) -> Result<Answer, AoCError> {
+        Err(AoCError::unsolvable("part 2 is not implemented"))
+    }
+    /// The parameters `part1_with` and `part2_with` read.
+    fn params(&self) -> &'static [Param] {
+        &[]
+    }
+    fn part1_with(&self, lines: &str, _params: &Params) -> Result<Answer, AoCError> {
+        self.part1(lines)
+    }
+    fn part2_with(&self, lines: &str, _params: &Params) -> Result<Answer, AoCError> {
+        self.part2(lines)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_error_position() {
+        let input = "1 2\n3 x 4";
+        let x = &input[6..7];
+        assert!(matches!(
+            AoCError::parse_at(input, x, "bad"),
+            AoCError::Parse {
+                line: 2,
+                column: 3,
+                ..
+            }
+        ));
+        assert!(matches!(
+            AoCError::parse_at(input, "elsewhere", "bad"),
+            AoCError::Parse {
+                line: 1,
+                column: 1,
+                ..
+            }
+        ));
+    }
+    #[test]
+    fn panics_become_errors() {
+        assert_eq!(catch_panic(|| Ok(Answer::from(1))).unwrap(), "1");
+        assert_eq!(
+            catch_panic(|| panic!("index {} out of range", 7))
+                .unwrap_err()
+                .to_string(),
+            "solver panicked: index 7 out of range"
+        );
+    }
+    #[test]
+    fn params() {
+        const SIZE: Param = Param::new("size", "70", "grid size");
+        const BLINKS: Param = Param::per_part("blinks", "25", "75", "times to blink");
+
+        let params = Params::default();
+        assert_eq!(params.get::<usize>(&SIZE, 2).unwrap(), 70);
+        assert_eq!(params.get::<usize>(&BLINKS, 1).unwrap(), 25);
+        assert_eq!(params.get::<usize>(&BLINKS, 2).unwrap(), 75);
+
+        let params = params.set("blinks", "40").set("size", "big");
+        assert_eq!(params.get::<usize>(&BLINKS, 1).unwrap(), 40);
+        assert_eq!(params.get::<usize>(&BLINKS, 2).unwrap(), 40);
+        assert_eq!(
+            params.get::<usize>(&SIZE, 1).unwrap_err().to_string(),
+            "invalid parameter size: 'big' is not a valid usize"
+        );
+        assert_eq!(params.names().collect::<Vec<_>>(), ["blinks", "size"]);
     }
-    fn part2(&self, _lines: &str) -> Option<String> {
-        None
+    #[test]
+    fn parse_number() {
+        let input = "12 y";
+        assert_eq!(parse::<i64>(input, &input[..2]).unwrap(), 12);
+        assert_eq!(
+            parse::<i64>(input, &input[3..]).unwrap_err().to_string(),
+            "line 1, column 4: 'y' is not a valid i64"
+        );
     }
 }
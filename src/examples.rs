@@ -0,0 +1,292 @@
+use crate::answers::{DayAnswers, PartAnswers};
+use crate::days::{AoCError, Day, Params};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// An example input from the puzzle text, with the answers it should give.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Example {
+    pub name: String,
+    pub input: String,
+    pub cases: Vec<Case>,
+}
+
+/// The answers an example should give with some parameter values, since the puzzle text often
+/// runs the same example with a smaller grid or a different threshold.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Case {
+    pub params: Params,
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+impl Case {
+    pub fn answer(&self, part: u32) -> Option<&str> {
+        match part {
+            1 => self.part1.as_deref(),
+            _ => self.part2.as_deref(),
+        }
+    }
+
+    /// The expected answers, to verify results against like a day's answers file.
+    pub fn answers(&self) -> DayAnswers {
+        let part = |answer: &Option<String>| PartAnswers {
+            answer: answer.clone(),
+            wrong: vec![],
+        };
+        DayAnswers {
+            part1: part(&self.part1),
+            part2: part(&self.part2),
+        }
+    }
+}
+
+impl Example {
+    /// Reads an example file: blocks of `part1: <answer>`, `part2: <answer>` and
+    /// `<param>: <value>` lines separated by blank lines, one block per case, then a `---` line,
+    /// then the input. Lines starting with `#` before the `---` are comments.
+    pub fn load(path: &Path) -> Result<Example, AoCError> {
+        let name = path.display().to_string();
+        let content = fs::read_to_string(path)?;
+        Example::parse(&name, &content).map_err(|e| AoCError::Example(name, e))
+    }
+
+    pub fn parse(name: &str, content: &str) -> Result<Example, String> {
+        let mut example = Example {
+            name: name.to_string(),
+            ..Example::default()
+        };
+        let mut case = Case::default();
+        let mut lines = content.split_inclusive('\n');
+        for line in lines.by_ref() {
+            let line = line.trim_end();
+            if line == "---" || line.is_empty() {
+                if case != Case::default() {
+                    example.cases.push(std::mem::take(&mut case));
+                }
+                if line == "---" {
+                    example.input = lines.collect::<String>().trim_end().to_string();
+                    return Ok(example);
+                }
+                continue;
+            }
+            if line.starts_with('#') {
+                continue;
+            }
+            match line
+                .split_once(':')
+                .map(|(key, value)| (key.trim(), value.trim()))
+            {
+                Some(("part1", answer)) => case.part1 = Some(answer.to_string()),
+                Some(("part2", answer)) => case.part2 = Some(answer.to_string()),
+                Some((name, value)) if !name.is_empty() && !name.contains(' ') => {
+                    case.params = std::mem::take(&mut case.params).set(name, value)
+                }
+                _ => {
+                    return Err(format!(
+                        "expected part1:, part2: or a parameter but found '{line}'"
+                    ))
+                }
+            }
+        }
+        Err("no '---' line before the input".to_string())
+    }
+
+    /// The example in the form [`Example::parse`] reads.
+    pub fn to_file(&self) -> String {
+        let mut content = String::new();
+        for (i, case) in self.cases.iter().enumerate() {
+            if i > 0 {
+                content += "\n";
+            }
+            for (name, value) in case.params.iter() {
+                content += &format!("{name}: {value}\n");
+            }
+            for (part, answer) in [(1, &case.part1), (2, &case.part2)] {
+                if let Some(answer) = answer {
+                    content += &format!("part{part}: {answer}\n");
+                }
+            }
+        }
+        content + "---\n" + &self.input + "\n"
+    }
+
+    /// How to refer to one of the example's cases in output, e.g. `day20/cheats.txt #2 (threshold=64)`.
+    pub fn case_name(&self, index: usize) -> String {
+        let mut name = self.name.clone();
+        if self.cases.len() > 1 {
+            name += &format!(" #{}", index + 1);
+        }
+        if !self.cases[index].params.is_empty() {
+            name += &format!(" ({})", self.cases[index].params);
+        }
+        name
+    }
+
+    /// Fails if a case sets a parameter that `day` doesn't have, which would otherwise be ignored.
+    pub fn check_params(&self, day: &dyn Day) -> Result<(), AoCError> {
+        for case in &self.cases {
+            for name in case.params.names() {
+                if !day.params().iter().any(|param| param.name == name) {
+                    return Err(AoCError::Example(
+                        self.name.clone(),
+                        format!("day {} has no parameter called {name}", day.day()),
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Where the examples are kept: `examples/dayN/*.txt` in the crate.
+pub fn dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("examples")
+}
+
+fn day_dir(dir: &Path, day: u32) -> PathBuf {
+    dir.join(format!("day{day}"))
+}
+
+/// The examples for `day` in `dir`, in file name order and named like `day20/cheats.txt`; none
+/// if it has no directory there.
+pub fn load(dir: &Path, day: u32) -> Result<Vec<Example>, AoCError> {
+    let day_dir = day_dir(dir, day);
+    if !day_dir.is_dir() {
+        return Ok(vec![]);
+    }
+
+    let mut files = vec![];
+    for entry in fs::read_dir(day_dir)? {
+        let file = entry?.path();
+        if file.extension().is_some_and(|e| e == "txt") {
+            files.push(file);
+        }
+    }
+    files.sort();
+    files
+        .iter()
+        .map(|file| {
+            let mut example = Example::load(file)?;
+            let file_name = file.file_name().unwrap_or_default().to_string_lossy();
+            example.name = format!("day{day}/{file_name}");
+            Ok(example)
+        })
+        .collect()
+}
+
+/// Writes `examples` for `day` into `dir` as `example1.txt`, `example2.txt` and so on.
+pub fn save(dir: &Path, day: u32, examples: &[Example]) -> Result<Vec<PathBuf>, AoCError> {
+    let day_dir = day_dir(dir, day);
+    fs::create_dir_all(&day_dir)?;
+    let mut paths = vec![];
+    for (i, example) in examples.iter().enumerate() {
+        let path = day_dir.join(format!("example{}.txt", i + 1));
+        fs::write(&path, example.to_file())?;
+        paths.push(path);
+    }
+    Ok(paths)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+
+    #[test]
+    fn parse_example() {
+        let example = Example::parse("e.txt", "part1: 11\npart2: 31\n---\n3   4\n4   3\n").unwrap();
+        assert_eq!(
+            example,
+            Example {
+                name: "e.txt".to_string(),
+                input: "3   4\n4   3".to_string(),
+                cases: vec![Case {
+                    params: Params::default(),
+                    part1: Some("11".to_string()),
+                    part2: Some("31".to_string()),
+                }],
+            }
+        );
+        assert!(Example::parse("e.txt", "part1: 11\n3   4\n").is_err());
+        assert!(Example::parse("e.txt", "part 3: 11\n---\n3   4\n").is_err());
+    }
+    #[test]
+    fn parse_cases() {
+        let text = "# from the puzzle text\nthreshold: 64\npart1: 1\n\nthreshold: 76\ncheat: 20\npart2: 3\n---\n#S.E#\n";
+        let example = Example::parse("cheats.txt", text).unwrap();
+        assert_eq!(example.cases.len(), 2);
+        assert_eq!(
+            example.cases[0].params,
+            Params::default().set("threshold", "64")
+        );
+        assert_eq!(example.cases[1].answer(2), Some("3"));
+        assert_eq!(example.cases[1].answer(1), None);
+        assert_eq!(example.input, "#S.E#");
+        assert_eq!(
+            example.case_name(1),
+            "cheats.txt #2 (cheat=20 threshold=76)"
+        );
+        assert_eq!(
+            Example::parse("cheats.txt", &example.to_file()).unwrap(),
+            Example {
+                cases: example.cases.clone(),
+                ..example
+            }
+        );
+    }
+    #[test]
+    fn unknown_param() {
+        let example = Example::parse("e.txt", "blinks: 6\npart1: 22\n---\n125 17").unwrap();
+        assert!(example.check_params(registry::get(11).unwrap()).is_ok());
+        assert!(matches!(
+            example.check_params(registry::get(9).unwrap()),
+            Err(AoCError::Example(_, _))
+        ));
+    }
+    #[test]
+    fn saves_and_loads() {
+        let dir = std::env::temp_dir().join(format!("aoc-examples-{}", std::process::id()));
+        let example = Example::parse("", "part2: 31\n---\n3   4").unwrap();
+
+        let paths = save(&dir, 1, std::slice::from_ref(&example)).unwrap();
+        let loaded = load(&dir, 1).unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].name, "day1/example1.txt");
+        assert!(paths[0].ends_with("day1/example1.txt"));
+        assert_eq!(loaded[0].cases, example.cases);
+        assert!(load(&dir, 2).unwrap().is_empty());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    /// Every example in `examples/` gives the answers it lists.
+    #[test]
+    fn examples() {
+        let mut failures = vec![];
+        for day in registry::days().unwrap() {
+            for example in load(&dir(), day.day()).unwrap() {
+                example.check_params(day).unwrap();
+                for (i, case) in example.cases.iter().enumerate() {
+                    for part in [1, 2] {
+                        let Some(expected) = case.answer(part) else {
+                            continue;
+                        };
+                        match crate::solve_with(day.day(), part, &example.input, &case.params) {
+                            Ok(answer) if answer == expected => {}
+                            Ok(answer) => failures.push(format!(
+                                "{} part {part}: expected {expected} but got {answer}",
+                                example.case_name(i)
+                            )),
+                            Err(e) => failures.push(format!(
+                                "{} part {part}: expected {expected} but failed: {e}",
+                                example.case_name(i)
+                            )),
+                        }
+                    }
+                }
+            }
+        }
+        assert!(failures.is_empty(), "{}", failures.join("\n"));
+    }
+}
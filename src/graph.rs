@@ -0,0 +1,236 @@
+//! Searches over graphs that are never built, only described by how to get from a node to its
+//! neighbours, like positions on a map or a reindeer's position and heading.
+
+use num::Zero;
+use priority_queue::PriorityQueue;
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// The nodes reachable in one step from each node. Any function from a node to its neighbours
+/// is one.
+pub trait Graph<N> {
+    fn neighbours(&self, node: &N) -> impl IntoIterator<Item = N>;
+}
+
+impl<N, F, I> Graph<N> for F
+where
+    F: Fn(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    fn neighbours(&self, node: &N) -> impl IntoIterator<Item = N> {
+        self(node)
+    }
+}
+
+/// How many steps it takes to reach each node reachable from `start`.
+pub fn bfs<N, G>(graph: &G, start: N) -> HashMap<N, usize>
+where
+    N: Clone + Eq + Hash,
+    G: Graph<N>,
+{
+    let mut distances = HashMap::from([(start.clone(), 0)]);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(node) = queue.pop_front() {
+        let distance = distances[&node] + 1;
+        for next in graph.neighbours(&node) {
+            if !distances.contains_key(&next) {
+                distances.insert(next.clone(), distance);
+                queue.push_back(next);
+            }
+        }
+    }
+    distances
+}
+
+/// The cheapest way to reach each node from `start`, where `cost` says what a step from one node
+/// to a neighbour costs. Costs mustn't be negative. Steps that cost nothing can make two nodes
+/// each as cheap to reach through the other; only the one reached first is counted as a
+/// predecessor of the other, so the paths never go round in circles.
+pub fn shortest_paths<N, G, C, F>(graph: &G, start: N, cost: F) -> ShortestPaths<N, C>
+where
+    N: Clone + Eq + Hash,
+    G: Graph<N>,
+    C: Copy + Ord + Add<Output = C> + Zero,
+    F: Fn(&N, &N) -> C,
+{
+    let mut paths = ShortestPaths {
+        costs: HashMap::from([(start.clone(), C::zero())]),
+        predecessors: HashMap::new(),
+        order: vec![],
+    };
+    let mut settled = HashSet::new();
+    let mut queue = PriorityQueue::new();
+    queue.push(start, Reverse(C::zero()));
+
+    while let Some((node, Reverse(node_cost))) = queue.pop() {
+        settled.insert(node.clone());
+        for next in graph.neighbours(&node) {
+            // its cost is final, and the nodes it came from are all settled before this one
+            if settled.contains(&next) {
+                continue;
+            }
+            let next_cost = node_cost + cost(&node, &next);
+            match paths.costs.get(&next) {
+                Some(&best) if best < next_cost => {}
+                Some(&best) if best == next_cost => {
+                    paths
+                        .predecessors
+                        .entry(next)
+                        .or_default()
+                        .push(node.clone());
+                }
+                _ => {
+                    paths.costs.insert(next.clone(), next_cost);
+                    paths.predecessors.insert(next.clone(), vec![node.clone()]);
+                    queue.push_increase(next, Reverse(next_cost));
+                }
+            }
+        }
+        paths.order.push(node);
+    }
+    paths
+}
+
+/// Every cheapest path from a start node, found by [`shortest_paths`].
+#[derive(Debug, Clone)]
+pub struct ShortestPaths<N, C> {
+    costs: HashMap<N, C>,
+    /// The nodes a cheapest path can come from, for each node but the start
+    predecessors: HashMap<N, Vec<N>>,
+    /// The nodes in the order they were settled, so cheapest first
+    order: Vec<N>,
+}
+
+impl<N: Clone + Eq + Hash, C: Copy> ShortestPaths<N, C> {
+    /// What it costs to reach `node`, or `None` if it can't be reached.
+    pub fn cost(&self, node: &N) -> Option<C> {
+        self.costs.get(node).copied()
+    }
+
+    /// The nodes that the cheapest paths to `node` come from, in the order they were found.
+    pub fn predecessors(&self, node: &N) -> &[N] {
+        self.predecessors.get(node).map_or(&[], Vec::as_slice)
+    }
+
+    /// How many different cheapest paths reach each reachable node.
+    pub fn path_counts(&self) -> HashMap<N, u64> {
+        let mut counts = HashMap::new();
+        for node in &self.order {
+            let count = match self.predecessors.get(node) {
+                Some(predecessors) => predecessors.iter().map(|p| counts[p]).sum(),
+                None => 1,
+            };
+            counts.insert(node.clone(), count);
+        }
+        counts
+    }
+
+    /// Every cheapest path to `target`, each from the start to `target`. There can be
+    /// exponentially many; [`ShortestPaths::path_counts`] says how many without listing them.
+    pub fn paths_to(&self, target: &N) -> Vec<Vec<N>> {
+        if !self.costs.contains_key(target) {
+            return vec![];
+        }
+        let mut paths = vec![];
+        let mut stack = vec![vec![target.clone()]];
+        while let Some(path) = stack.pop() {
+            let predecessors = self.predecessors(path.last().unwrap());
+            if predecessors.is_empty() {
+                paths.push(path.into_iter().rev().collect());
+                continue;
+            }
+            for predecessor in predecessors.iter().rev() {
+                let mut longer = path.clone();
+                longer.push(predecessor.clone());
+                stack.push(longer);
+            }
+        }
+        paths
+    }
+
+    /// Every node on some cheapest path to any of `targets`, the targets included.
+    pub fn nodes_on_paths_to(&self, targets: impl IntoIterator<Item = N>) -> HashSet<N> {
+        let mut nodes = HashSet::new();
+        let mut stack: Vec<_> = targets
+            .into_iter()
+            .filter(|target| self.costs.contains_key(target))
+            .collect();
+        while let Some(node) = stack.pop() {
+            if nodes.insert(node.clone()) {
+                stack.extend(self.predecessors(&node).iter().cloned());
+            }
+        }
+        nodes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A diamond, 0 to 3 by 1 or 2, then a dearer shortcut from 0 to 4 and a step on from 3.
+    fn diamond(node: &u32) -> Vec<u32> {
+        match node {
+            0 => vec![1, 2, 4],
+            1 | 2 => vec![3],
+            3 => vec![4],
+            _ => vec![],
+        }
+    }
+
+    fn cost(from: &u32, to: &u32) -> u32 {
+        if (*from, *to) == (0, 4) {
+            5
+        } else {
+            1
+        }
+    }
+
+    #[test]
+    fn distances() {
+        let distances = bfs(&diamond, 0);
+        assert_eq!(distances.len(), 5);
+        assert_eq!(distances[&3], 2);
+        assert_eq!(distances[&4], 1);
+        assert_eq!(bfs(&diamond, 3), HashMap::from([(3, 0), (4, 1)]));
+    }
+    #[test]
+    fn cheapest() {
+        let paths = shortest_paths(&diamond, 0, cost);
+        assert_eq!(paths.cost(&4), Some(3));
+        assert_eq!(paths.cost(&0), Some(0));
+        assert_eq!(paths.predecessors(&3), [1, 2]);
+        assert!(paths.predecessors(&0).is_empty());
+
+        let unreachable = shortest_paths(&diamond, 1, cost);
+        assert_eq!(unreachable.cost(&2), None);
+        assert!(unreachable.paths_to(&2).is_empty());
+    }
+    #[test]
+    fn all_paths() {
+        let paths = shortest_paths(&diamond, 0, cost);
+        assert_eq!(paths.paths_to(&4), [[0, 1, 3, 4], [0, 2, 3, 4]]);
+        assert_eq!(paths.path_counts()[&4], 2);
+        assert_eq!(paths.path_counts()[&0], 1);
+        assert_eq!(paths.nodes_on_paths_to([3]), HashSet::from([0, 1, 2, 3]));
+    }
+    #[test]
+    fn free_steps() {
+        // 1 and 2 are a step from 0 and free to go between
+        let graph = |node: &u32| match node {
+            0 => vec![1, 2],
+            1 => vec![2],
+            _ => vec![1],
+        };
+        let paths = shortest_paths(&graph, 0, |&from: &u32, _: &u32| u32::from(from == 0));
+        assert_eq!((paths.cost(&1), paths.cost(&2)), (Some(1), Some(1)));
+
+        let counts = paths.path_counts();
+        assert_eq!(counts[&1] + counts[&2], 3);
+        assert_eq!(paths.paths_to(&1).len() + paths.paths_to(&2).len(), 3);
+        assert!(paths.predecessors(&0).is_empty());
+    }
+}
@@ -0,0 +1,270 @@
+use crate::compass::{Dir4, Dir8};
+use crate::days::AoCError;
+use std::fmt::{Display, Formatter};
+use std::ops::{Index, IndexMut};
+
+/// A cell's column and row, counting from the top left.
+pub type Pos = (usize, usize);
+
+/// A rectangular map of cells, like the puzzles' character maps.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Reads a map with one row per line, turning each character into a cell with `cell`, whose
+    /// error is reported at that character. Fails if the map is empty or its rows differ in
+    /// length.
+    pub fn parse<F>(input: &str, cell: F) -> Result<Grid<T>, AoCError>
+    where
+        F: FnMut(char) -> Result<T, String>,
+    {
+        Grid::parse_within(input, input, cell)
+    }
+
+    /// [`Grid::parse`] for a map in `text`, a slice of `input`, with errors located in `input`.
+    pub(crate) fn parse_within<F>(input: &str, text: &str, mut cell: F) -> Result<Grid<T>, AoCError>
+    where
+        F: FnMut(char) -> Result<T, String>,
+    {
+        let mut grid = Grid {
+            width: 0,
+            height: 0,
+            cells: vec![],
+        };
+        for line in text.lines() {
+            for (i, c) in line.char_indices() {
+                grid.cells
+                    .push(cell(c).map_err(|e| AoCError::parse_at(input, &line[i..], e))?);
+            }
+            let width = line.chars().count();
+            if grid.height == 0 {
+                grid.width = width;
+            } else if width != grid.width {
+                return Err(AoCError::parse_at(
+                    input,
+                    line,
+                    format!("expected {} columns but found {width}", grid.width),
+                ));
+            }
+            grid.height += 1;
+        }
+        if grid.cells.is_empty() {
+            return Err(AoCError::parse_at(input, text, "empty map"));
+        }
+        Ok(grid)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Whether `(x, y)` is on the map; signed, so a step off the top or left edge can be asked
+    /// about.
+    pub fn contains(&self, x: i64, y: i64) -> bool {
+        x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height
+    }
+
+    /// The cell at `(x, y)`, or `None` off the map.
+    pub fn get(&self, x: i64, y: i64) -> Option<&T> {
+        self.contains(x, y)
+            .then(|| &self.cells[y as usize * self.width + x as usize])
+    }
+
+    pub fn get_mut(&mut self, x: i64, y: i64) -> Option<&mut T> {
+        if self.contains(x, y) {
+            Some(&mut self.cells[y as usize * self.width + x as usize])
+        } else {
+            None
+        }
+    }
+
+    /// Where `offset` from `pos` lands, if it's still on the map.
+    pub fn step(&self, pos: Pos, offset: (i64, i64)) -> Option<Pos> {
+        let (x, y) = (pos.0 as i64 + offset.0, pos.1 as i64 + offset.1);
+        self.contains(x, y).then_some((x as usize, y as usize))
+    }
+
+    /// The orthogonal neighbours of `pos` on the map, clockwise from the one above.
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        Dir4::ALL
+            .iter()
+            .filter_map(move |dir| self.step(pos, dir.offset()))
+    }
+
+    /// The neighbours of `pos` on the map including diagonals, clockwise from the one above.
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        Dir8::ALL
+            .iter()
+            .filter_map(move |dir| self.step(pos, dir.offset()))
+    }
+
+    /// Every position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// Every cell with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    /// Every cell, row by row.
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    /// A grid of the same shape with `f` applied to each cell.
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// The first position, row by row, whose cell matches `predicate`.
+    pub fn find<P: FnMut(&T) -> bool>(&self, mut predicate: P) -> Option<Pos> {
+        self.iter()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(pos, _)| pos)
+    }
+
+    /// Every position whose cell matches `predicate`, row by row.
+    pub fn find_all<'a, P: FnMut(&T) -> bool + 'a>(
+        &'a self,
+        mut predicate: P,
+    ) -> impl Iterator<Item = Pos> + 'a {
+        self.iter()
+            .filter(move |(_, cell)| predicate(cell))
+            .map(|(pos, _)| pos)
+    }
+
+    /// The map as text again, a line per row, with `f` drawing each cell.
+    pub fn render<F: FnMut(&T) -> char>(&self, mut f: F) -> String {
+        let mut text = String::with_capacity((self.width + 1) * self.height);
+        for row in self.cells.chunks(self.width.max(1)) {
+            text.extend(row.iter().map(&mut f));
+            text.push('\n');
+        }
+        text
+    }
+}
+
+impl Grid<char> {
+    /// Reads a map keeping its characters as they are.
+    pub fn chars(input: &str) -> Result<Grid<char>, AoCError> {
+        Grid::parse(input, Ok)
+    }
+}
+
+/// Unchecked indexing, for positions already known to be on the map.
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): Pos) -> &T {
+        assert!(
+            x < self.width,
+            "column {x} is outside a grid {} wide",
+            self.width
+        );
+        &self.cells[y * self.width + x]
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, (x, y): Pos) -> &mut T {
+        assert!(
+            x < self.width,
+            "column {x} is outside a grid {} wide",
+            self.width
+        );
+        &mut self.cells[y * self.width + x]
+    }
+}
+
+impl Display for Grid<char> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.render(|&c| c))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let grid = Grid::chars("#S.\n..E\n").unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(1, 0)], 'S');
+        assert_eq!(grid.find(|&c| c == 'E'), Some((2, 1)));
+        assert_eq!(grid.find_all(|&c| c == '.').count(), 3);
+        assert_eq!(grid.to_string(), "#S.\n..E\n");
+
+        let walls = grid.map(|&c| c == '#');
+        assert_eq!(walls.render(|&w| if w { '#' } else { '.' }), "#..\n...\n");
+    }
+    #[test]
+    fn parse_errors() {
+        let digits = |input| {
+            Grid::parse(input, |c| {
+                c.to_digit(10).ok_or(format!("{c} is not a digit"))
+            })
+            .map_err(|e| e.to_string())
+        };
+        assert_eq!(
+            digits("012\n3x5").unwrap_err(),
+            "line 2, column 2: x is not a digit"
+        );
+        assert_eq!(
+            digits("012\n34").unwrap_err(),
+            "line 2, column 1: expected 3 columns but found 2"
+        );
+        assert_eq!(digits("").unwrap_err(), "line 1, column 1: empty map");
+    }
+    #[test]
+    fn bounds() {
+        let mut grid = Grid::new(3, 2, 0);
+        assert_eq!(grid.get(2, 1), Some(&0));
+        assert_eq!(grid.get(3, 1), None);
+        assert_eq!(grid.get(-1, 0), None);
+        *grid.get_mut(0, 1).unwrap() = 5;
+        assert_eq!(grid[(0, 1)], 5);
+        assert_eq!(grid.step((0, 1), (1, -1)), Some((1, 0)));
+        assert_eq!(grid.step((0, 1), (0, 1)), None);
+    }
+    #[test]
+    fn neighbours() {
+        let grid = Grid::new(3, 3, ());
+        assert_eq!(
+            grid.neighbours4((0, 0)).collect::<Vec<_>>(),
+            [(1, 0), (0, 1)]
+        );
+        assert_eq!(grid.neighbours4((1, 1)).count(), 4);
+        assert_eq!(
+            grid.neighbours8((0, 0)).collect::<Vec<_>>(),
+            [(1, 0), (1, 1), (0, 1)]
+        );
+        assert_eq!(grid.neighbours8((1, 1)).count(), 8);
+    }
+}
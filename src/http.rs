@@ -0,0 +1,167 @@
+use crate::config::Config;
+use crate::days::AoCError;
+use crate::unlock::Clock;
+use chrono::DateTime;
+use reqwest::blocking::{Client, Response};
+use std::fs;
+use std::time::Duration;
+
+fn last_request_filename(config: &Config) -> String {
+    format!("{}/.last-request", config.input_dir)
+}
+
+pub fn client(config: &Config) -> Result<Client, AoCError> {
+    Ok(Client::builder().user_agent(&config.user_agent).build()?)
+}
+
+/// Waits until at least `config.request_interval` has passed since the last request made by any
+/// run, then records this request's time.
+pub fn throttle(config: &Config, clock: &impl Clock) -> Result<(), AoCError> {
+    let filename = last_request_filename(config);
+
+    let last = fs::read_to_string(&filename)
+        .ok()
+        .and_then(|content| DateTime::parse_from_rfc3339(content.trim()).ok());
+    if let Some(last) = last {
+        // a last request in the future means the clock moved back, so wait the full interval
+        let elapsed = (clock.now() - last.to_utc())
+            .to_std()
+            .unwrap_or(Duration::ZERO);
+        if let Some(remaining) = config.request_interval.checked_sub(elapsed) {
+            if !remaining.is_zero() {
+                clock.sleep(remaining);
+            }
+        }
+    }
+
+    fs::create_dir_all(&config.input_dir)?;
+    fs::write(&filename, clock.now().to_rfc3339())?;
+    Ok(())
+}
+
+pub fn check_status(url: &str, response: Response) -> Result<Response, AoCError> {
+    let status = response.status();
+    if status.is_success() {
+        Ok(response)
+    } else {
+        Err(AoCError::HttpStatus(url.to_string(), status.as_u16()))
+    }
+}
+
+#[cfg(test)]
+pub mod stand_in {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+    use std::thread;
+
+    /// Serves each canned `(status, body)` to one request in turn, sending each request back
+    /// down the channel. Returns the base URL to point the client at.
+    pub fn server(responses: Vec<(u16, &'static str)>) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let (sender, receiver) = mpsc::channel();
+
+        thread::spawn(move || {
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+                let mut request = String::new();
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(length) = line.to_lowercase().strip_prefix("content-length:") {
+                        content_length = length.trim().parse().unwrap();
+                    }
+                    request += &line;
+                    if line == "\r\n" {
+                        break;
+                    }
+                }
+                let mut content = vec![0; content_length];
+                reader.read_exact(&mut content).unwrap();
+                request += &String::from_utf8(content).unwrap();
+                sender.send(request).unwrap();
+
+                write!(
+                    stream,
+                    "HTTP/1.1 {status} Stand-in\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+        });
+
+        (base_url, receiver)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::unlock::FakeClock;
+
+    fn config(name: &str) -> Config {
+        let dir = std::env::temp_dir().join(format!("aoc-http-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        Config {
+            input_dir: dir.to_str().unwrap().to_string(),
+            request_interval: Duration::from_secs(5),
+            ..Config::default()
+        }
+    }
+
+    #[test]
+    fn first_request_does_not_wait() {
+        let config = config("first");
+        let clock = FakeClock::at("2024-12-17T05:00:00Z");
+
+        throttle(&config, &clock).unwrap();
+
+        assert_eq!(clock.now(), FakeClock::at("2024-12-17T05:00:00Z").now());
+        fs::remove_dir_all(&config.input_dir).unwrap();
+    }
+    #[test]
+    fn waits_for_interval_across_runs() {
+        let config = config("interval");
+
+        throttle(&config, &FakeClock::at("2024-12-17T05:00:00Z")).unwrap();
+
+        let clock = FakeClock::at("2024-12-17T05:00:02Z");
+        throttle(&config, &clock).unwrap();
+        assert_eq!(clock.now(), FakeClock::at("2024-12-17T05:00:05Z").now());
+
+        let clock = FakeClock::at("2024-12-17T06:00:00Z");
+        throttle(&config, &clock).unwrap();
+        assert_eq!(clock.now(), FakeClock::at("2024-12-17T06:00:00Z").now());
+
+        fs::remove_dir_all(&config.input_dir).unwrap();
+    }
+    #[test]
+    fn sends_user_agent() {
+        let (base_url, requests) = stand_in::server(vec![(200, "ok")]);
+        let config = Config {
+            user_agent: "test-agent/1.0".to_string(),
+            ..Config::default()
+        };
+
+        client(&config).unwrap().get(base_url).send().unwrap();
+
+        assert!(requests
+            .recv()
+            .unwrap()
+            .to_lowercase()
+            .contains("user-agent: test-agent/1.0"));
+    }
+    #[test]
+    fn error_status() {
+        let (base_url, _requests) = stand_in::server(vec![(404, "Not Found")]);
+        let response = Client::new().get(&base_url).send().unwrap();
+        assert!(matches!(
+            check_status(&base_url, response),
+            Err(AoCError::HttpStatus(_, 404))
+        ));
+    }
+}
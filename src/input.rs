@@ -0,0 +1,263 @@
+use crate::config::Config;
+use crate::days::AoCError;
+use crate::http;
+use crate::output::InputSource;
+use crate::unlock::{self, Clock, SystemClock};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Where and when a downloaded input came from, kept next to the input itself.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct InputMetadata {
+    fetched_at: String,
+    url: String,
+    status: u16,
+    bytes: usize,
+}
+
+pub fn input_filename(config: &Config, day: u32, _part: u32) -> String {
+    format!("{}/day{day}.txt", config.input_dir)
+}
+
+fn metadata_filename(config: &Config, day: u32) -> String {
+    format!("{}/day{day}.meta.toml", config.input_dir)
+}
+
+pub fn get_input(config: &Config, day: u32, part: u32) -> Result<(String, InputSource), AoCError> {
+    get_input_with(config, &SystemClock, day, part)
+}
+
+fn get_input_with(
+    config: &Config,
+    clock: &impl Clock,
+    day: u32,
+    part: u32,
+) -> Result<(String, InputSource), AoCError> {
+    match fs::read_to_string(input_filename(config, day, part))
+        .or_else(|_| fs::read_to_string(input_filename(config, day, 1)))
+    {
+        Ok(content) => Ok((content, InputSource::Cache)),
+        Err(_) if unlock::is_unlocked(clock, config.year, day) => {
+            download(config, clock, day, part).map(|content| (content, InputSource::Download))
+        }
+        Err(e) => Err(AoCError::from(e)),
+    }
+}
+
+/// Inputs named on the command line rather than fetched for a day, with their names: the file at
+/// `path`, stdin for `-`, or every `*.txt` file in a directory.
+pub fn read_inputs(path: &str) -> Result<Vec<(String, String, InputSource)>, AoCError> {
+    if path == "-" {
+        let content = io::read_to_string(io::stdin())?;
+        return Ok(vec![(path.to_string(), content, InputSource::Stdin)]);
+    }
+    if !Path::new(path).is_dir() {
+        let content = fs::read_to_string(path)?;
+        return Ok(vec![(path.to_string(), content, InputSource::File)]);
+    }
+
+    let mut files = vec![];
+    for entry in fs::read_dir(path)? {
+        let file = entry?.path();
+        if file.extension().is_some_and(|e| e == "txt") && file.is_file() {
+            files.push(file);
+        }
+    }
+    if files.is_empty() {
+        return Err(AoCError::InvalidParameter(
+            "input".to_string(),
+            format!("there are no .txt files in {path}"),
+        ));
+    }
+    files.sort();
+
+    files
+        .into_iter()
+        .map(|file| {
+            let content = fs::read_to_string(&file)?;
+            Ok((file.display().to_string(), content, InputSource::File))
+        })
+        .collect()
+}
+
+fn download(config: &Config, clock: &impl Clock, day: u32, part: u32) -> Result<String, AoCError> {
+    let url = format!("{}/{}/day/{day}/input", config.base_url, config.year);
+    let cookie = config.cookie()?;
+
+    http::throttle(config, clock)?;
+    let response = http::client(config)?
+        .get(&url)
+        .header("Cookie", cookie)
+        .send()?;
+    let response = http::check_status(&url, response)?;
+    let status = response.status().as_u16();
+    let content = response.text()?;
+
+    // only reached on success, so an error page never ends up cached as input
+    fs::create_dir_all(&config.input_dir)?;
+    fs::write(input_filename(config, day, part), &content)?;
+
+    let metadata = InputMetadata {
+        fetched_at: clock.now().to_rfc3339(),
+        url,
+        status,
+        bytes: content.len(),
+    };
+    fs::write(
+        metadata_filename(config, day),
+        toml::to_string(&metadata).expect("metadata is always serialisable"),
+    )?;
+
+    Ok(content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::stand_in;
+    use crate::unlock::FakeClock;
+
+    fn metadata(config: &Config, day: u32) -> Option<InputMetadata> {
+        let content = fs::read_to_string(metadata_filename(config, day)).ok()?;
+        toml::from_str(&content).ok()
+    }
+
+    fn config(name: &str, base_url: &str) -> Config {
+        let dir = std::env::temp_dir().join(format!("aoc-input-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        Config {
+            input_dir: dir.to_str().unwrap().to_string(),
+            base_url: base_url.to_string(),
+            session: Some("abc".to_string()),
+            ..Config::default()
+        }
+    }
+
+    #[test]
+    fn reads_from_input_dir() {
+        let dir = std::env::temp_dir().join(format!("aoc-input-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("day3.txt"), "mul(2,3)").unwrap();
+
+        let config = Config {
+            input_dir: dir.to_str().unwrap().to_string(),
+            ..Config::default()
+        };
+        assert_eq!(
+            get_input(&config, 3, 2).unwrap(),
+            ("mul(2,3)".to_string(), InputSource::Cache)
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+    #[test]
+    fn explicit_inputs() {
+        let dir = std::env::temp_dir().join(format!("aoc-inputs-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("bob.txt"), "2").unwrap();
+        fs::write(dir.join("alice.txt"), "1").unwrap();
+        fs::write(dir.join("alice.toml"), "").unwrap();
+        let dir_name = dir.to_str().unwrap();
+
+        let inputs = read_inputs(dir_name).unwrap();
+        assert_eq!(
+            inputs,
+            [
+                (
+                    format!("{dir_name}/alice.txt"),
+                    "1".to_string(),
+                    InputSource::File
+                ),
+                (
+                    format!("{dir_name}/bob.txt"),
+                    "2".to_string(),
+                    InputSource::File
+                ),
+            ]
+        );
+        assert_eq!(read_inputs(&inputs[1].0).unwrap(), inputs[1..]);
+
+        fs::remove_file(dir.join("alice.txt")).unwrap();
+        fs::remove_file(dir.join("bob.txt")).unwrap();
+        assert!(matches!(
+            read_inputs(dir_name),
+            Err(AoCError::InvalidParameter(_, _))
+        ));
+        assert!(matches!(
+            read_inputs("/nonexistent.txt"),
+            Err(AoCError::Io(_))
+        ));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+    #[test]
+    fn future_puzzles_are_not_fetched() {
+        let config = Config {
+            year: 9999,
+            input_dir: "/nonexistent".to_string(),
+            ..Config::default()
+        };
+        assert!(matches!(get_input(&config, 1, 1), Err(AoCError::Io(_))));
+    }
+    #[test]
+    fn missing_session_is_an_error() {
+        let config = Config {
+            year: 2015,
+            input_dir: "/nonexistent".to_string(),
+            session_file: "/nonexistent/.cookie".to_string(),
+            ..Config::default()
+        };
+        assert!(matches!(
+            get_input(&config, 1, 1),
+            Err(AoCError::CookieFile(_))
+        ));
+    }
+    #[test]
+    fn downloads_and_records_metadata() {
+        let (base_url, requests) = stand_in::server(vec![(200, "3   4\n4   3\n")]);
+        let config = config("download", &base_url);
+        let clock = FakeClock::at("2024-12-17T05:00:00Z");
+
+        assert_eq!(
+            get_input_with(&config, &clock, 1, 1).unwrap(),
+            ("3   4\n4   3\n".to_string(), InputSource::Download)
+        );
+
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("GET /2024/day/1/input "));
+        assert!(request.to_lowercase().contains("cookie: session=abc"));
+
+        assert_eq!(
+            fs::read_to_string(input_filename(&config, 1, 1)).unwrap(),
+            "3   4\n4   3\n"
+        );
+        assert_eq!(
+            metadata(&config, 1),
+            Some(InputMetadata {
+                fetched_at: "2024-12-17T05:00:00+00:00".to_string(),
+                url: format!("{base_url}/2024/day/1/input"),
+                status: 200,
+                bytes: 12,
+            })
+        );
+
+        fs::remove_dir_all(&config.input_dir).unwrap();
+    }
+    #[test]
+    fn error_pages_are_not_saved() {
+        let (base_url, _requests) = stand_in::server(vec![(400, "Puzzle inputs differ by user.")]);
+        let config = config("error", &base_url);
+        let clock = FakeClock::at("2024-12-17T05:00:00Z");
+
+        assert!(matches!(
+            get_input_with(&config, &clock, 1, 1),
+            Err(AoCError::HttpStatus(_, 400))
+        ));
+        assert!(!fs::exists(input_filename(&config, 1, 1)).unwrap());
+        assert_eq!(metadata(&config, 1), None);
+
+        let _ = fs::remove_dir_all(&config.input_dir);
+    }
+}
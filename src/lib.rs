@@ -0,0 +1,109 @@
+//! Advent of Code 2024 solutions, and the tooling to fetch inputs, run, check and submit them.
+
+include!(concat!(env!("OUT_DIR"), "/days.rs"));
+
+pub mod answer;
+pub mod answers;
+pub mod args;
+pub mod bench;
+pub mod cancel;
+pub mod compass;
+pub mod config;
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day2;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod days;
+pub mod examples;
+pub mod graph;
+pub mod grid;
+pub mod http;
+pub mod input;
+pub mod memo;
+pub mod output;
+pub mod parallel;
+pub mod parsing;
+pub mod registry;
+pub mod report;
+pub mod runner;
+pub mod scaffold;
+pub mod submit;
+pub mod unlock;
+pub mod watch;
+
+pub use crate::days::{Answer, AoCError, Day, Param, Params};
+
+/// Solves `part` of `day`'s puzzle for `input`.
+pub fn solve(day: u32, part: u32, input: &str) -> Result<Answer, AoCError> {
+    solve_with(day, part, input, &Params::default())
+}
+
+/// Solves `part` of `day`'s puzzle for `input`, with `params` in place of the puzzle's own.
+pub fn solve_with(day: u32, part: u32, input: &str, params: &Params) -> Result<Answer, AoCError> {
+    let solver = registry::get(day).ok_or_else(|| {
+        AoCError::InvalidParameter("day".to_string(), format!("there is no day {day}"))
+    })?;
+    if !solver.parts().contains(&part) {
+        return Err(AoCError::InvalidParameter(
+            "part".to_string(),
+            format!("day {day} has no part {part}"),
+        ));
+    }
+
+    days::catch_panic(|| match part {
+        1 => solver.part1_with(input.trim(), params),
+        _ => solver.part2_with(input.trim(), params),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solve_by_number() {
+        assert_eq!(solve(9, 1, "2333133121414131402\n").unwrap(), "1928");
+        assert_eq!(solve(9, 2, "2333133121414131402").unwrap(), "2858");
+    }
+    #[test]
+    fn solve_with_params() {
+        let params = Params::default().set("blinks", "6");
+        assert_eq!(solve_with(11, 1, "125 17", &params).unwrap(), "22");
+    }
+    #[test]
+    fn solve_unknown() {
+        assert!(matches!(
+            solve(26, 1, ""),
+            Err(AoCError::InvalidParameter(_, _))
+        ));
+        assert!(matches!(
+            solve(9, 3, ""),
+            Err(AoCError::InvalidParameter(_, _))
+        ));
+        assert!(matches!(
+            solve(25, 2, ""),
+            Err(AoCError::InvalidParameter(_, _))
+        ));
+    }
+}
@@ -1,132 +1,217 @@
 extern crate core;
 
-mod day1;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day14;
-mod day15;
-mod day16;
-mod day17;
-mod day18;
-mod day19;
-mod day2;
-mod day20;
-mod day21;
-mod day22;
-mod day23;
-mod day24;
-mod day25;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
-mod days;
-
-use crate::days::AoCError;
-use chrono;
-use chrono::NaiveDate;
-use reqwest;
-use std::fs;
-use std::time::Instant;
-
-const EASTERN_STANDARD_TIME: chrono::FixedOffset =
-    chrono::FixedOffset::west_opt(4 * 60 * 60).unwrap();
-
-fn input_filename(day: u32, _part: u32) -> String {
-    format!("input/day{day}.txt")
-}
+mod cli;
 
-fn is_in_past(day: u32) -> bool {
-    let now = chrono::Local::now();
-    let this_day = NaiveDate::from_ymd_opt(2024, 12, day)
-        .unwrap()
-        .and_hms_opt(0, 0, 0)
-        .unwrap()
-        .and_local_timezone(EASTERN_STANDARD_TIME)
-        .unwrap();
+use crate::cli::{Cli, Command};
+use advent_of_code_2024::bench::BenchOptions;
+use advent_of_code_2024::config::Config;
+use advent_of_code_2024::days::{AoCError, Params};
+use advent_of_code_2024::examples::{self, Case, Example};
+use advent_of_code_2024::runner::{self, Baseline, Inputs};
+use advent_of_code_2024::unlock::{self, SystemClock};
+use advent_of_code_2024::{registry, scaffold, submit, watch};
+use clap::Parser;
+use std::collections::BTreeSet;
+use std::io::Write;
+use std::path::Path;
+use std::process::ExitCode;
 
-    now > this_day
+/// Success unless something `failed` or went wrong.
+fn exit_code(failed: Result<bool, AoCError>) -> ExitCode {
+    match failed {
+        Ok(false) => ExitCode::SUCCESS,
+        Ok(true) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
 }
 
-fn read_cookie() -> String {
-    fs::read_to_string(".cookie")
-        .map_err(|e| AoCError::CookieFile(e))
-        .unwrap()
+/// Submits `answer`, failing unless it is correct.
+fn submit(config: &Config, day: u32, part: u32, answer: &str) -> Result<bool, AoCError> {
+    let outcome = submit::submit(config, day, part, answer)?;
+    println!("Day {day} part {part}: {answer}: {outcome}");
+    Ok(outcome != submit::Outcome::Correct)
 }
 
-fn get_input(day: u32, part: u32) -> Result<String, AoCError> {
-    fs::read_to_string(input_filename(day, part))
-        .or_else(|_| fs::read_to_string(input_filename(day, 1)))
-        .or_else(|e| {
-            if is_in_past(day) {
-                reqwest::blocking::Client::new()
-                    .get(format!("https://adventofcode.com/2024/day/{day}/input"))
-                    .header("Cookie", read_cookie())
-                    .send()
-                    .and_then(|r| r.text())
-                    .inspect(|content| {
-                        let _ = fs::create_dir("input");
-                        let _ = fs::write(input_filename(day, part), content);
-                    })
-                    .map_err(AoCError::from)
-            } else {
-                Err(AoCError::from(e))
-            }
-        })
+/// Creates the module for `day` with the examples from the `example` files and `input`.
+fn new_day(
+    day: u32,
+    example: &[String],
+    input: &Option<String>,
+    part1: &Option<String>,
+    part2: &Option<String>,
+) -> Result<bool, AoCError> {
+    let mut examples = vec![];
+    for path in example {
+        examples.push(Example::load(Path::new(path))?);
+    }
+    if let Some(input) = input {
+        examples.push(Example {
+            name: "--input".to_string(),
+            input: input.clone(),
+            cases: vec![Case {
+                params: Params::default(),
+                part1: part1.clone(),
+                part2: part2.clone(),
+            }],
+        });
+    }
+
+    let src_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+    for path in scaffold::new_day(&src_dir, &examples::dir(), day, &examples)? {
+        println!("Created {}", path.display());
+    }
+    Ok(false)
 }
 
-fn run_part<F>(day: u32, part: u32, run: F)
-where
-    F: Fn(&str) -> Option<String>,
-{
-    if let Some((result, duration)) = get_input(day, part).ok().and_then(|content| {
-        let start = Instant::now();
-        run(content.trim()).map(|r| (r, Instant::now().duration_since(start)))
-    }) {
-        print!("\tPart {part}:\t{result}\t({duration:?}");
+/// The options `watch` passes on to each run of `day`.
+fn watch_options(cli: &Cli) -> Vec<String> {
+    let mut options = vec![];
+    if let Some(config) = &cli.config {
+        options.extend(["--config".to_string(), config.clone()]);
+    }
+    if let Some(part) = cli.part {
+        options.extend(["--part".to_string(), part.to_string()]);
     }
+    for (name, value) in &cli.params {
+        options.extend(["--param".to_string(), format!("{name}={value}")]);
+    }
+    options
+}
+
+/// Waits for the single day asked for, or else the next puzzle, to unlock, counting down.
+fn wait(cli: &Cli, config: &Config) -> Result<u32, String> {
+    let day = match &cli.day {
+        Some(days) if days.len() == 1 => days.first().copied(),
+        Some(_) => {
+            return Err("--wait needs a single --day, or none to wait for the next puzzle".into())
+        }
+        None => unlock::next_locked_day(&SystemClock, config.year),
+    };
+    let day =
+        day.ok_or_else(|| format!("Every puzzle for {} has already unlocked", config.year))?;
+
+    unlock::wait_for_unlock(&SystemClock, config.year, day, |remaining| {
+        eprint!(
+            "\rDay {day} unlocks in {}",
+            unlock::format_remaining(remaining)
+        );
+        let _ = std::io::stderr().flush();
+    });
+    eprintln!();
+    Ok(day)
 }
 
-fn main() {
-    let days: Vec<Box<&dyn days::Day>> = vec![
-        Box::new(&day25::Day),
-        Box::new(&day24::Day),
-        Box::new(&day23::Day),
-        Box::new(&day22::Day),
-        Box::new(&day21::Day),
-        Box::new(&day20::Day),
-        Box::new(&day19::Day),
-        Box::new(&day18::Day),
-        Box::new(&day17::Day),
-        Box::new(&day16::Day),
-        Box::new(&day15::Day),
-        Box::new(&day14::Day),
-        Box::new(&day13::Day),
-        Box::new(&day12::Day),
-        Box::new(&day11::Day),
-        Box::new(&day10::Day),
-        Box::new(&day9::Day),
-        Box::new(&day8::Day),
-        Box::new(&day7::Day),
-        Box::new(&day6::Day),
-        Box::new(&day5::Day),
-        Box::new(&day4::Day),
-        Box::new(&day3::Day),
-        Box::new(&day2::Day),
-        Box::new(&day1::Day),
-    ];
-
-    for day in days {
-        let number = day.day();
-
-        print!("Day {number}:");
-        run_part(number, 2, |l| day.part2(l).map(|r| r.to_string()));
-        run_part(number, 1, |l| day.part1(l).map(|r| r.to_string()));
-        println!();
+fn main() -> ExitCode {
+    let mut cli = Cli::parse();
+
+    let days = match registry::days() {
+        Ok(days) => days,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut config = match Config::load(cli.config.as_deref()) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match &cli.command {
+        Some(Command::Submit {
+            day,
+            part,
+            answer,
+            base_url,
+        }) => {
+            if let Some(base_url) = base_url {
+                config.base_url = base_url.clone();
+            }
+            return exit_code(submit(&config, *day, *part, answer));
+        }
+        Some(Command::NewDay {
+            day,
+            example,
+            input,
+            part1,
+            part2,
+        }) => return exit_code(new_day(*day, example, input, part1, part2)),
+        _ => {}
     }
+
+    if let Some(timeout) = cli.timeout {
+        config.timeout = Some(timeout);
+    }
+
+    if let Some(Command::Watch { day, interval }) = &cli.command {
+        let watched = watch::watch(&config, *day, &watch_options(&cli), *interval);
+        return exit_code(watched.map(|()| false));
+    }
+
+    if cli.wait {
+        match wait(&cli, &config) {
+            Ok(day) => cli.day = Some(BTreeSet::from([day])),
+            Err(e) => {
+                eprintln!("{e}");
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    let days: Vec<_> = days
+        .into_iter()
+        .filter(|day| cli.wants_day(day.day()))
+        .collect();
+
+    if cli.list {
+        for day in days {
+            println!("{}", registry::describe(day));
+        }
+        return ExitCode::SUCCESS;
+    }
+
+    let selection = cli.selection();
+    if let Err(e) = runner::check_params(&days, &selection.params) {
+        eprintln!("{e}");
+        return ExitCode::FAILURE;
+    }
+
+    if cli.bench {
+        let options = BenchOptions {
+            warmup: cli.warmup,
+            runs: cli.runs,
+            budget: cli.budget,
+        };
+        let baseline = Baseline {
+            compare: cli.baseline.clone(),
+            save: cli.save_baseline.clone(),
+            threshold: cli.threshold,
+        };
+        let failed = runner::bench_days(&config, &days, &selection, &options, &baseline);
+        return exit_code(Ok(failed));
+    }
+
+    let inputs = match &cli.input {
+        Some(_) if days.len() != 1 => {
+            eprintln!("--input needs a single --day");
+            return ExitCode::FAILURE;
+        }
+        Some(path) => Inputs::Files(path.clone()),
+        None if cli.examples => Inputs::Examples,
+        None => Inputs::Puzzle,
+    };
+    exit_code(runner::run_days(
+        &config,
+        &days,
+        &selection,
+        &inputs,
+        cli.workers(),
+        cli.report.as_deref(),
+    ))
 }
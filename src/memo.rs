@@ -0,0 +1,186 @@
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::hash::Hash;
+use std::rc::Rc;
+
+/// A cache of a function's results for a single solve: create one where the search starts and
+/// pass it down, so nothing is shared between parts, inputs or tests.
+///
+/// It only needs `&self`, so a recursive closure can use the memo that's calling it. One made
+/// within [`collect`] also counts its lookups towards that call's totals.
+#[derive(Debug)]
+pub struct Memo<K, V> {
+    cache: RefCell<HashMap<K, V>>,
+    hits: Cell<u64>,
+    misses: Cell<u64>,
+    totals: Option<Totals>,
+}
+
+/// The combined stats of the memos made within a [`collect`], `None` until one is made.
+type Totals = Rc<Cell<Option<Stats>>>;
+
+/// How often a [`Memo`] found what it was asked for.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub struct Stats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+thread_local! {
+    /// The totals of the [`collect`] this thread is in, if any.
+    static COLLECTING: RefCell<Option<Totals>> = const { RefCell::new(None) };
+}
+
+/// Runs `f`, returning its result with the combined stats of the memos it made, or `None` if it
+/// made none. Memos made outside it count nothing but their own lookups.
+pub fn collect<R, F: FnOnce() -> R>(f: F) -> (R, Option<Stats>) {
+    let totals = Totals::default();
+    let outer = COLLECTING.with(|collecting| collecting.replace(Some(totals.clone())));
+    let result = f();
+    COLLECTING.with(|collecting| collecting.replace(outer));
+    (result, totals.get())
+}
+
+impl Stats {
+    /// The fraction of lookups that were hits, or 0 before any.
+    pub fn hit_rate(&self) -> f64 {
+        match self.hits + self.misses {
+            0 => 0.0,
+            lookups => self.hits as f64 / lookups as f64,
+        }
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} hits, {} misses ({:.1}% hit rate)",
+            self.hits,
+            self.misses,
+            self.hit_rate() * 100.0
+        )
+    }
+}
+
+impl<K: Hash + Eq, V: Clone> Memo<K, V> {
+    pub fn new() -> Memo<K, V> {
+        let totals = COLLECTING.with(|collecting| collecting.borrow().clone());
+        if let Some(totals) = &totals {
+            totals.set(Some(totals.get().unwrap_or_default()));
+        }
+        Memo {
+            cache: RefCell::new(HashMap::new()),
+            hits: Cell::new(0),
+            misses: Cell::new(0),
+            totals,
+        }
+    }
+
+    /// The result for `key`, computing it with `compute` the first time it's asked for.
+    /// `compute` may look up other keys in the same memo.
+    pub fn get_or_insert_with<F: FnOnce() -> V>(&self, key: K, compute: F) -> V {
+        if let Some(value) = self.cache.borrow().get(&key) {
+            self.hits.set(self.hits.get() + 1);
+            self.count(|total| total.hits += 1);
+            return value.clone();
+        }
+        self.misses.set(self.misses.get() + 1);
+        self.count(|total| total.misses += 1);
+        // not borrowed while computing, which may recurse
+        let value = compute();
+        self.cache.borrow_mut().insert(key, value.clone());
+        value
+    }
+
+    /// How many results it holds.
+    pub fn len(&self) -> usize {
+        self.cache.borrow().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn count(&self, lookup: impl FnOnce(&mut Stats)) {
+        if let Some(totals) = &self.totals {
+            let mut total = totals.get().unwrap_or_default();
+            lookup(&mut total);
+            totals.set(Some(total));
+        }
+    }
+
+    pub fn stats(&self) -> Stats {
+        Stats {
+            hits: self.hits.get(),
+            misses: self.misses.get(),
+        }
+    }
+}
+
+impl<K: Hash + Eq, V: Clone> Default for Memo<K, V> {
+    fn default() -> Memo<K, V> {
+        Memo::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fibonacci(memo: &Memo<u64, u64>, n: u64) -> u64 {
+        memo.get_or_insert_with(n, || match n {
+            0 | 1 => n,
+            _ => fibonacci(memo, n - 1) + fibonacci(memo, n - 2),
+        })
+    }
+
+    #[test]
+    fn recursion() {
+        let memo = Memo::new();
+        assert_eq!(fibonacci(&memo, 90), 2880067194370816120);
+        assert_eq!(memo.len(), 91);
+        assert_eq!(
+            memo.stats(),
+            Stats {
+                hits: 88,
+                misses: 91
+            }
+        );
+
+        assert_eq!(fibonacci(&memo, 90), 2880067194370816120);
+        assert_eq!(memo.stats().hits, 89);
+    }
+    #[test]
+    fn stats() {
+        assert_eq!(Stats::default().hit_rate(), 0.0);
+        let stats = Stats { hits: 3, misses: 1 };
+        assert_eq!(stats.hit_rate(), 0.75);
+        assert_eq!(stats.to_string(), "3 hits, 1 misses (75.0% hit rate)");
+    }
+    #[test]
+    fn collects() {
+        let (_, totals) = collect(|| {
+            for n in [10, 20] {
+                fibonacci(&Memo::new(), n);
+            }
+        });
+        assert_eq!(
+            totals,
+            Some(Stats {
+                hits: 8 + 18,
+                misses: 11 + 21
+            })
+        );
+        assert_eq!(
+            collect(|| fibonacci(&Memo::new(), 1)).1,
+            Some(Stats { hits: 0, misses: 1 })
+        );
+        assert_eq!(collect(|| 1), (1, None));
+
+        // a memo made outside doesn't count
+        let memo = Memo::new();
+        assert_eq!(collect(|| fibonacci(&memo, 5)), (5, None));
+    }
+}
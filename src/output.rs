@@ -0,0 +1,432 @@
+use crate::answers::Verdict;
+use crate::bench::BenchResult;
+use crate::days::Answer;
+use crate::memo;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InputSource {
+    Cache,
+    Download,
+    File,
+    Stdin,
+    Example,
+}
+
+/// Deserializes from the JSON format, so that results can be read back from another process.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PartResult {
+    pub day: u32,
+    pub part: u32,
+    pub answer: Option<Answer>,
+    #[serde(
+        rename = "duration_ns",
+        serialize_with = "serialize_nanos",
+        deserialize_with = "deserialize_nanos"
+    )]
+    pub duration: Option<Duration>,
+    /// CPU time the solver's thread spent on the part, where the platform can measure it
+    #[serde(skip)]
+    pub cpu_time: Option<Duration>,
+    pub source: Option<InputSource>,
+    pub success: bool,
+    pub error: Option<String>,
+    pub verdict: Verdict,
+    /// The file given with `--input`, when not solving the day's own puzzle input
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input: Option<String>,
+    /// How the solver's memos did, if it used any
+    #[serde(skip)]
+    pub memo: Option<memo::Stats>,
+}
+
+fn serialize_nanos<S: serde::Serializer>(
+    duration: &Option<Duration>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match duration {
+        Some(duration) => serializer.serialize_u64(duration.as_nanos() as u64),
+        None => serializer.serialize_none(),
+    }
+}
+
+fn deserialize_nanos<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<Duration>, D::Error> {
+    Ok(Option::<u64>::deserialize(deserializer)?.map(Duration::from_nanos))
+}
+
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum Format {
+    /// One tab-separated line per day
+    #[default]
+    Text,
+    /// One JSON object per line per part
+    Json,
+    /// A header row, then one row per part
+    Csv,
+}
+
+const CSV_HEADER: &str = "day,part,answer,duration_ns,source,success,error,verdict,input";
+const BENCH_CSV_HEADER: &str =
+    "day,part,answer,runs,min_ns,median_ns,mean_ns,p95_ns,std_dev_ns,change,regression";
+
+#[derive(Serialize)]
+struct BenchRecord<'a> {
+    #[serde(flatten)]
+    result: &'a BenchResult,
+    change: Option<f64>,
+    regression: bool,
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+impl Format {
+    pub fn header(&self) -> Option<String> {
+        match self {
+            Format::Csv => Some(format!("{CSV_HEADER}\n")),
+            _ => None,
+        }
+    }
+
+    pub fn bench_header(&self) -> Option<String> {
+        match self {
+            Format::Csv => Some(format!("{BENCH_CSV_HEADER}\n")),
+            _ => None,
+        }
+    }
+
+    pub fn day_start(&self, day: u32, input: Option<&str>) -> Option<String> {
+        match self {
+            Format::Text => match input {
+                Some(input) => Some(format!("Day {day} ({input}):")),
+                None => Some(format!("Day {day}:")),
+            },
+            _ => None,
+        }
+    }
+
+    pub fn day_end(&self) -> Option<String> {
+        match self {
+            Format::Text => Some("\n".to_string()),
+            _ => None,
+        }
+    }
+
+    /// Totals for the whole run: wall time from first part started to last finished, and CPU
+    /// time summed over every part.
+    pub fn summary(&self, wall: Duration, cpu: Duration) -> Option<String> {
+        match self {
+            Format::Text => Some(format!("Total:\t{wall:?} wall\t{cpu:?} CPU\n")),
+            _ => None,
+        }
+    }
+
+    pub fn part(&self, result: &PartResult) -> Option<String> {
+        match self {
+            Format::Text => {
+                // a part whose input could not be loaded never ran, so has nothing to report
+                let duration = result.duration?;
+                let mut text = match (&result.answer, &result.error) {
+                    (Some(answer), _) if result.success => {
+                        format!("\tPart {}:\t{answer}\t({duration:?})", result.part)
+                    }
+                    (_, Some(error)) => {
+                        format!("\tPart {}:\terror: {error}\t({duration:?})", result.part)
+                    }
+                    _ => return None,
+                };
+                if result.verdict != Verdict::Unknown {
+                    text += &format!(" {}", result.verdict);
+                }
+                if let Some(memo) = result.memo {
+                    text += &format!("\tmemo: {memo}");
+                }
+                Some(text)
+            }
+            Format::Json => Some(format!("{}\n", serde_json::to_string(result).unwrap())),
+            Format::Csv => Some(format!(
+                "{},{},{},{},{},{},{},{},{}\n",
+                result.day,
+                result.part,
+                csv_field(
+                    &result
+                        .answer
+                        .as_ref()
+                        .map_or(String::new(), |a| a.to_string())
+                ),
+                result
+                    .duration
+                    .map(|d| d.as_nanos().to_string())
+                    .unwrap_or_default(),
+                match result.source {
+                    Some(InputSource::Cache) => "cache",
+                    Some(InputSource::Download) => "download",
+                    Some(InputSource::File) => "file",
+                    Some(InputSource::Stdin) => "stdin",
+                    Some(InputSource::Example) => "example",
+                    None => "",
+                },
+                result.success,
+                csv_field(result.error.as_deref().unwrap_or("")),
+                result.verdict.name(),
+                csv_field(result.input.as_deref().unwrap_or("")),
+            )),
+        }
+    }
+
+    pub fn bench(&self, result: &BenchResult, change: Option<f64>, regression: bool) -> String {
+        let stats = &result.stats;
+        match self {
+            Format::Text => {
+                let d = Duration::from_nanos;
+                let mut line = format!(
+                    "Day {} Part {}:\t{}\tmin {:?}\tmedian {:?}\tmean {:?}\tp95 {:?}\tsd {:?}\t({} runs)",
+                    result.day,
+                    result.part,
+                    result.answer.as_deref().unwrap_or("-"),
+                    d(stats.min_ns),
+                    d(stats.median_ns),
+                    d(stats.mean_ns),
+                    d(stats.p95_ns),
+                    d(stats.std_dev_ns),
+                    stats.runs,
+                );
+                if let Some(change) = change {
+                    line += &format!("\t{:+.1}%", change * 100.0);
+                }
+                if regression {
+                    line += "\tREGRESSION";
+                }
+                line + "\n"
+            }
+            Format::Json => {
+                let record = BenchRecord {
+                    result,
+                    change,
+                    regression,
+                };
+                format!("{}\n", serde_json::to_string(&record).unwrap())
+            }
+            Format::Csv => format!(
+                "{},{},{},{},{},{},{},{},{},{},{}\n",
+                result.day,
+                result.part,
+                csv_field(
+                    &result
+                        .answer
+                        .as_ref()
+                        .map_or(String::new(), |a| a.to_string())
+                ),
+                stats.runs,
+                stats.min_ns,
+                stats.median_ns,
+                stats.mean_ns,
+                stats.p95_ns,
+                stats.std_dev_ns,
+                change.map(|c| c.to_string()).unwrap_or_default(),
+                regression,
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result() -> PartResult {
+        PartResult {
+            day: 17,
+            part: 1,
+            answer: Some(Answer::list([4, 6, 3])),
+            duration: Some(Duration::from_nanos(1500)),
+            cpu_time: Some(Duration::from_nanos(1200)),
+            source: Some(InputSource::Cache),
+            success: true,
+            error: None,
+            verdict: Verdict::Unknown,
+            input: None,
+            memo: None,
+        }
+    }
+
+    #[test]
+    fn text() {
+        assert_eq!(
+            Format::Text.part(&result()),
+            Some("\tPart 1:\t4,6,3\t(1.5µs)".to_string())
+        );
+    }
+    #[test]
+    fn text_verdict() {
+        let result = PartResult {
+            verdict: Verdict::KnownWrong,
+            ..result()
+        };
+        assert_eq!(
+            Format::Text.part(&result),
+            Some("\tPart 1:\t4,6,3\t(1.5µs) KNOWN WRONG".to_string())
+        );
+    }
+    #[test]
+    fn text_memo() {
+        let result = PartResult {
+            memo: Some(memo::Stats { hits: 3, misses: 1 }),
+            ..result()
+        };
+        assert_eq!(
+            Format::Text.part(&result),
+            Some("\tPart 1:\t4,6,3\t(1.5µs)\tmemo: 3 hits, 1 misses (75.0% hit rate)".to_string())
+        );
+    }
+    #[test]
+    fn text_solver_error() {
+        let result = PartResult {
+            answer: None,
+            success: false,
+            error: Some("no solution: the exit cannot be reached".to_string()),
+            ..result()
+        };
+        assert_eq!(
+            Format::Text.part(&result),
+            Some("\tPart 1:\terror: no solution: the exit cannot be reached\t(1.5µs)".to_string())
+        );
+    }
+    #[test]
+    fn text_missing_input_is_silent() {
+        let result = PartResult {
+            answer: None,
+            duration: None,
+            source: None,
+            success: false,
+            error: Some("an IO error occurred".to_string()),
+            ..result()
+        };
+        assert_eq!(Format::Text.part(&result), None);
+    }
+    #[test]
+    fn summary() {
+        let (wall, cpu) = (Duration::from_millis(250), Duration::from_millis(900));
+        assert_eq!(
+            Format::Text.summary(wall, cpu),
+            Some("Total:\t250ms wall\t900ms CPU\n".to_string())
+        );
+        assert_eq!(Format::Json.summary(wall, cpu), None);
+        assert_eq!(Format::Csv.summary(wall, cpu), None);
+    }
+    #[test]
+    fn json() {
+        assert_eq!(
+            Format::Json.part(&result()),
+            Some(
+                r#"{"day":17,"part":1,"answer":[4,6,3],"duration_ns":1500,"source":"cache","success":true,"error":null,"verdict":"unknown"}
+"#
+                .to_string()
+            )
+        );
+    }
+    #[test]
+    fn csv() {
+        assert_eq!(Format::Csv.header(), Some(format!("{CSV_HEADER}\n")));
+        assert_eq!(
+            Format::Csv.part(&result()),
+            Some("17,1,\"4,6,3\",1500,cache,true,,unknown,\n".to_string())
+        );
+    }
+    fn bench_result() -> BenchResult {
+        BenchResult {
+            day: 11,
+            part: 2,
+            answer: Some("65601038650482".to_string()),
+            stats: crate::bench::Stats {
+                runs: 10,
+                min_ns: 1000,
+                median_ns: 1500,
+                mean_ns: 1600,
+                p95_ns: 2000,
+                std_dev_ns: 300,
+            },
+        }
+    }
+    #[test]
+    fn bench_text() {
+        assert_eq!(
+            Format::Text.bench(&bench_result(), Some(0.25), true),
+            "Day 11 Part 2:\t65601038650482\tmin 1µs\tmedian 1.5µs\tmean 1.6µs\tp95 2µs\tsd 300ns\t(10 runs)\t+25.0%\tREGRESSION\n"
+        );
+    }
+    #[test]
+    fn bench_json() {
+        assert_eq!(
+            Format::Json.bench(&bench_result(), None, false),
+            r#"{"day":11,"part":2,"answer":"65601038650482","stats":{"runs":10,"min_ns":1000,"median_ns":1500,"mean_ns":1600,"p95_ns":2000,"std_dev_ns":300},"change":null,"regression":false}
+"#
+        );
+    }
+    #[test]
+    fn bench_csv() {
+        assert_eq!(
+            Format::Csv.bench(&bench_result(), Some(-0.5), false),
+            "11,2,65601038650482,10,1000,1500,1600,2000,300,-0.5,false\n"
+        );
+    }
+    #[test]
+    fn explicit_input() {
+        let result = PartResult {
+            source: Some(InputSource::File),
+            input: Some("inputs/alice.txt".to_string()),
+            ..result()
+        };
+        assert_eq!(
+            Format::Text.day_start(17, Some("inputs/alice.txt")),
+            Some("Day 17 (inputs/alice.txt):".to_string())
+        );
+        assert_eq!(
+            Format::Json.part(&result),
+            Some(
+                r#"{"day":17,"part":1,"answer":[4,6,3],"duration_ns":1500,"source":"file","success":true,"error":null,"verdict":"unknown","input":"inputs/alice.txt"}
+"#
+                .to_string()
+            )
+        );
+        assert_eq!(
+            Format::Csv.part(&result),
+            Some("17,1,\"4,6,3\",1500,file,true,,unknown,inputs/alice.txt\n".to_string())
+        );
+    }
+    #[test]
+    fn json_round_trip() {
+        let json = Format::Json.part(&result()).unwrap();
+        let expected = PartResult {
+            // not part of the output
+            cpu_time: None,
+            ..result()
+        };
+        assert_eq!(serde_json::from_str::<PartResult>(&json).unwrap(), expected);
+    }
+    #[test]
+    fn csv_failure() {
+        let result = PartResult {
+            answer: None,
+            duration: None,
+            source: None,
+            success: false,
+            error: Some("an IO error occurred".to_string()),
+            ..result()
+        };
+        assert_eq!(
+            Format::Csv.part(&result),
+            Some("17,1,,,,false,an IO error occurred,unknown,\n".to_string())
+        );
+    }
+}
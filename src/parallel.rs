@@ -0,0 +1,98 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+/// Runs `f` over `jobs` on up to `workers` threads, returning the results in job order.
+pub fn run_all<T, R, F>(jobs: Vec<T>, workers: usize, f: F) -> Vec<R>
+where
+    T: Send,
+    R: Send,
+    F: Fn(T) -> R + Sync,
+{
+    let workers = workers.clamp(1, jobs.len().max(1));
+    let jobs: Vec<Mutex<Option<T>>> = jobs.into_iter().map(|j| Mutex::new(Some(j))).collect();
+    let results: Vec<Mutex<Option<R>>> = jobs.iter().map(|_| Mutex::new(None)).collect();
+    let next = AtomicUsize::new(0);
+
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(job) = jobs.get(i) else {
+                    break;
+                };
+                let job = job.lock().unwrap().take().unwrap();
+                let result = f(job);
+                *results[i].lock().unwrap() = Some(result);
+            });
+        }
+    });
+
+    results
+        .into_iter()
+        .map(|r| r.into_inner().unwrap().unwrap())
+        .collect()
+}
+
+/// The number of workers to use when asked for zero, meaning one per CPU.
+pub fn default_workers() -> usize {
+    thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+/// CPU time used so far by the calling thread.
+#[cfg(unix)]
+pub fn thread_cpu_time() -> Option<Duration> {
+    let mut time = libc::timespec {
+        tv_sec: 0,
+        tv_nsec: 0,
+    };
+    // SAFETY: `time` is a valid timespec for the call to write into
+    let status = unsafe { libc::clock_gettime(libc::CLOCK_THREAD_CPUTIME_ID, &mut time) };
+    (status == 0).then(|| Duration::new(time.tv_sec as u64, time.tv_nsec as u32))
+}
+
+#[cfg(not(unix))]
+pub fn thread_cpu_time() -> Option<Duration> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn results_in_job_order() {
+        let jobs: Vec<u64> = (0..50).collect();
+        let results = run_all(jobs, 4, |n| {
+            // make later jobs finish first
+            thread::sleep(Duration::from_micros(50 - n));
+            n * n
+        });
+        assert_eq!(results, (0..50).map(|n| n * n).collect::<Vec<_>>());
+    }
+    #[test]
+    fn uses_several_threads() {
+        let threads = run_all((0..8).collect(), 4, |_: i32| {
+            thread::sleep(Duration::from_millis(10));
+            thread::current().id()
+        });
+        assert!(threads.iter().collect::<HashSet<_>>().len() > 1);
+    }
+    #[test]
+    fn no_jobs() {
+        assert_eq!(run_all(Vec::<i32>::new(), 4, |n| n), Vec::<i32>::new());
+    }
+    #[test]
+    fn cpu_time_advances() {
+        let Some(before) = thread_cpu_time() else {
+            return;
+        };
+        let mut n = 0u64;
+        for i in 0..1_000_000 {
+            n = std::hint::black_box(n.wrapping_add(i));
+        }
+        assert!(thread_cpu_time().unwrap() > before);
+    }
+}
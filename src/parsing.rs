@@ -0,0 +1,184 @@
+//! Helpers for reading puzzle inputs. Each takes the whole `input` along with the slice of it
+//! being read, so that errors say which line and column they are on.
+
+use crate::days::{self, AoCError};
+use crate::grid::Grid;
+use std::str::FromStr;
+
+/// The blocks of `text` separated by blank lines.
+pub fn sections(text: &str) -> impl Iterator<Item = &str> {
+    text.split("\n\n")
+        .map(|section| section.trim_matches('\n'))
+        .filter(|section| !section.is_empty())
+}
+
+/// The two blocks of `input` either side of its first blank line, like a map and the moves on
+/// it. `expected` describes the layout for the error when there's no blank line.
+pub fn two_sections<'a>(input: &'a str, expected: &str) -> Result<(&'a str, &'a str), AoCError> {
+    input.split_once("\n\n").ok_or_else(|| {
+        AoCError::parse_at(input, &input[input.len()..], format!("expected {expected}"))
+    })
+}
+
+/// The next of `lines`, or an empty line at the end of `input` when there are no more, so a
+/// missing line is reported where it should have been.
+pub fn next_line<'a>(input: &'a str, lines: &mut impl Iterator<Item = &'a str>) -> &'a str {
+    lines.next().unwrap_or(&input[input.len()..])
+}
+
+/// The items of `text` between `separator`s, like `75,47,61`.
+pub fn separated<T: FromStr>(input: &str, text: &str, separator: &str) -> Result<Vec<T>, AoCError> {
+    text.split(separator)
+        .map(|item| days::parse(input, item))
+        .collect()
+}
+
+/// Every integer in `text`, with its sign, whatever is around them: `Button A: X+94, Y-34`
+/// gives 94 and -34.
+pub fn integers<T: FromStr>(input: &str, text: &str) -> Result<Vec<T>, AoCError> {
+    let bytes = text.as_bytes();
+    let digit_at = |i: usize| bytes.get(i).is_some_and(u8::is_ascii_digit);
+
+    let mut integers = vec![];
+    let mut i = 0;
+    while i < bytes.len() {
+        let start = i;
+        if bytes[i] == b'-' && digit_at(i + 1) {
+            i += 1;
+        }
+        if digit_at(i) {
+            while digit_at(i) {
+                i += 1;
+            }
+            integers.push(days::parse(input, &text[start..i])?);
+        } else {
+            i = start + 1;
+        }
+    }
+    Ok(integers)
+}
+
+/// What each `{name}` placeholder in `template` matches in `line`, in order: with
+/// `Register {name}: {value}`, `Register A: 729` gives `A` and `729`. A placeholder matches up to
+/// the first occurrence of the text after it, or the rest of the line if it comes last. The
+/// error for a line that doesn't match shows the template with `<name>` for each placeholder.
+pub fn fields<'a, const N: usize>(
+    input: &str,
+    line: &'a str,
+    template: &str,
+) -> Result<[&'a str; N], AoCError> {
+    let mismatch = || {
+        let expected = template.replace('{', "<").replace('}', ">");
+        AoCError::parse_at(input, line, format!("expected {expected}"))
+    };
+
+    let mut pieces = template.split('{');
+    let prefix = pieces.next().unwrap_or_default();
+    let mut rest = line.strip_prefix(prefix).ok_or_else(mismatch)?;
+    let mut fields = [""; N];
+    let mut count = 0;
+    for piece in pieces {
+        let (_, literal) = piece
+            .split_once('}')
+            .unwrap_or_else(|| panic!("unclosed placeholder in {template}"));
+        let (field, after) = match literal {
+            "" => (rest, ""),
+            _ => rest.split_once(literal).ok_or_else(mismatch)?,
+        };
+        if count < N {
+            fields[count] = field;
+        }
+        count += 1;
+        rest = after;
+    }
+    assert_eq!(count, N, "{template} has {count} placeholders, not {N}");
+
+    if rest.is_empty() {
+        Ok(fields)
+    } else {
+        Err(mismatch())
+    }
+}
+
+/// A map in `block`, a part of `input` such as one of its [`sections`], with a cell from each
+/// character. See [`Grid::parse`].
+pub fn grid<T, F>(input: &str, block: &str, cell: F) -> Result<Grid<T>, AoCError>
+where
+    F: FnMut(char) -> Result<T, String>,
+{
+    Grid::parse_within(input, block, cell)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_sections() {
+        let input = "47|53\n97|13\n\n75,47\n\n\n61,53\n";
+        assert_eq!(
+            sections(input).collect::<Vec<_>>(),
+            ["47|53\n97|13", "75,47", "61,53"]
+        );
+        assert_eq!(
+            two_sections(input, "rules").unwrap(),
+            ("47|53\n97|13", "75,47\n\n\n61,53\n")
+        );
+        assert_eq!(
+            two_sections("47|53", "rules, a blank line, then updates")
+                .unwrap_err()
+                .to_string(),
+            "line 1, column 6: expected rules, a blank line, then updates"
+        );
+    }
+    #[test]
+    fn numbers() {
+        let input = "Button A: X+94, Y-34\n1,x,3";
+        let (first, second) = input.split_once('\n').unwrap();
+        assert_eq!(integers::<i64>(input, first).unwrap(), [94, -34]);
+        assert!(integers::<i64>(input, "a - b").unwrap().is_empty());
+        assert_eq!(
+            separated::<u8>(input, second, ",").unwrap_err().to_string(),
+            "line 2, column 3: 'x' is not a valid u8"
+        );
+        assert_eq!(separated::<u8>(input, "1,2", ",").unwrap(), [1, 2]);
+    }
+    #[test]
+    fn matches_templates() {
+        let input = "Register A: 729\nx00 AND y00 -> z00\nRegister B 0";
+        let mut lines = input.lines();
+        let [name, value] =
+            fields(input, lines.next().unwrap(), "Register {name}: {value}").unwrap();
+        assert_eq!((name, value), ("A", "729"));
+
+        let [l, op, r, out] =
+            fields(input, lines.next().unwrap(), "{l} {op} {r} -> {out}").unwrap();
+        assert_eq!([l, op, r, out], ["x00", "AND", "y00", "z00"]);
+
+        let line = next_line(input, &mut lines);
+        assert_eq!(
+            fields::<2>(input, line, "Register {name}: {value}")
+                .unwrap_err()
+                .to_string(),
+            "line 3, column 1: expected Register <name>: <value>"
+        );
+        assert!(fields::<1>(input, "Register A: 7 8", "Register A: {value} ").is_err());
+        assert_eq!(next_line(input, &mut lines), "");
+    }
+    #[test]
+    fn grid_block() {
+        let input = "#####\n.####\n\n.....\n#.x..";
+        let block = sections(input).nth(1).unwrap();
+        let filled = |c| match c {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            _ => Err(format!("{c} is not # or .")),
+        };
+        assert_eq!(
+            grid(input, block, filled).unwrap_err().to_string(),
+            "line 5, column 3: x is not # or ."
+        );
+        let lock = grid(input, sections(input).next().unwrap(), filled).unwrap();
+        assert_eq!((lock.width(), lock.height()), (5, 2));
+    }
+}
@@ -0,0 +1,101 @@
+use crate::days::{AoCError, Day};
+use std::collections::BTreeSet;
+
+/// Every day, newest first, once the modules have been checked.
+pub fn days() -> Result<Vec<&'static dyn Day>, AoCError> {
+    check(crate::DAY_MODULES)
+}
+
+/// The solver for `day`, if there is one.
+pub fn get(day: u32) -> Option<&'static dyn Day> {
+    crate::DAY_MODULES
+        .iter()
+        .find(|&&(number, _)| number == day)
+        .map(|&(_, day)| day)
+}
+
+/// A line saying which parts `day` implements and the parameters it takes, with their defaults.
+pub fn describe(day: &dyn Day) -> String {
+    let mut line = format!("Day {}:", day.day());
+    for part in day.parts() {
+        line += &format!("\tPart {part}");
+    }
+    for param in day.params() {
+        match param.defaults {
+            [part1, part2] if part1 == part2 => line += &format!("\t{}={part1}", param.name),
+            [part1, part2] => line += &format!("\t{}={part1}/{part2}", param.name),
+        }
+    }
+    line
+}
+
+/// Checks that no day has two modules and that each module's `Day` is for the day in its file
+/// name, returning the days newest first.
+pub fn check(modules: &[(u32, &'static dyn Day)]) -> Result<Vec<&'static dyn Day>, AoCError> {
+    let mut seen = BTreeSet::new();
+    for &(number, day) in modules {
+        if day.day() != number {
+            return Err(AoCError::Registry(format!(
+                "the module for day {number} says it is day {}",
+                day.day()
+            )));
+        }
+        if !seen.insert(number) {
+            return Err(AoCError::Registry(format!(
+                "there is more than one module for day {number}"
+            )));
+        }
+    }
+    Ok(modules.iter().rev().map(|&(_, day)| day).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Fake(u32);
+
+    impl Day for Fake {
+        fn day(&self) -> u32 {
+            self.0
+        }
+    }
+
+    #[test]
+    fn every_module_is_registered() {
+        let days = days().unwrap();
+        assert_eq!(
+            days.iter().map(|day| day.day()).collect::<Vec<_>>(),
+            (1..=25).rev().collect::<Vec<_>>()
+        );
+    }
+    #[test]
+    fn lookup() {
+        assert_eq!(get(17).unwrap().day(), 17);
+        assert!(get(26).is_none());
+    }
+    #[test]
+    fn descriptions() {
+        assert_eq!(describe(&Fake(3)), "Day 3:\tPart 1\tPart 2");
+        assert_eq!(
+            describe(get(11).unwrap()),
+            "Day 11:\tPart 1\tPart 2\tblinks=25/75"
+        );
+    }
+    #[test]
+    fn mismatched_day() {
+        static DAYS: [(u32, &dyn Day); 2] = [(1, &Fake(1)), (2, &Fake(3))];
+        assert_eq!(
+            check(&DAYS).err().unwrap().to_string(),
+            "invalid day registry: the module for day 2 says it is day 3"
+        );
+    }
+    #[test]
+    fn duplicate_day() {
+        static DAYS: [(u32, &dyn Day); 2] = [(1, &Fake(1)), (1, &Fake(1))];
+        assert_eq!(
+            check(&DAYS).err().unwrap().to_string(),
+            "invalid day registry: there is more than one module for day 1"
+        );
+    }
+}
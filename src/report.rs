@@ -0,0 +1,306 @@
+use crate::answers::Verdict;
+use crate::days::AoCError;
+use crate::output::PartResult;
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::time::Duration;
+
+/// How many runs the trend charts go back.
+const TREND_RUNS: usize = 20;
+
+/// A slowdown against the previous run bigger than this is highlighted.
+const SLOWER: f64 = 0.1;
+
+/// One run's results, as kept in the history file: one JSON object per line.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Run {
+    /// When the run finished, in RFC 3339
+    pub at: String,
+    pub results: Vec<PartResult>,
+}
+
+impl Run {
+    /// How long `part` of `day` took on `input`, `None` being the puzzle input.
+    fn duration(&self, day: u32, part: u32, input: Option<&str>) -> Option<Duration> {
+        self.results
+            .iter()
+            .find(|r| r.day == day && r.part == part && r.input.as_deref() == input && r.success)
+            .and_then(|r| r.duration)
+    }
+}
+
+/// The runs recorded in `path`, oldest first; none if it doesn't exist yet.
+pub fn load_history(path: &str) -> Result<Vec<Run>, AoCError> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(AoCError::from(e)),
+    };
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            serde_json::from_str(line).map_err(|e| {
+                AoCError::Io(std::io::Error::new(
+                    ErrorKind::InvalidData,
+                    format!("{path}: {e}"),
+                ))
+            })
+        })
+        .collect()
+}
+
+pub fn append_history(path: &str, run: &Run) -> Result<(), AoCError> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", serde_json::to_string(run).unwrap())?;
+    Ok(())
+}
+
+/// Writes the HTML report of `results` to `path`, adding them to the history first if `record`.
+pub fn write(
+    path: &str,
+    history_file: &str,
+    record: bool,
+    results: Vec<PartResult>,
+    wall: Duration,
+    cpu: Duration,
+) -> Result<(), AoCError> {
+    let history = load_history(history_file)?;
+    let run = Run {
+        at: Local::now().to_rfc3339(),
+        results,
+    };
+    if record {
+        append_history(history_file, &run)?;
+    }
+    fs::write(path, render(&run, &history, wall, cpu))?;
+    Ok(())
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Relative change against the run before, e.g. `0.25` for 25% slower.
+fn change(previous: Option<Duration>, current: Option<Duration>) -> Option<f64> {
+    let (previous, current) = (previous?.as_nanos() as f64, current?.as_nanos() as f64);
+    (previous > 0.0).then(|| (current - previous) / previous)
+}
+
+/// A line chart of a part's times over the runs, left to right, as inline SVG.
+fn sparkline(durations: &[Option<Duration>]) -> String {
+    const WIDTH: f64 = 120.0;
+    const HEIGHT: f64 = 24.0;
+    let max = durations
+        .iter()
+        .flatten()
+        .max()
+        .copied()
+        .unwrap_or_default();
+    if max.is_zero() {
+        return String::new();
+    }
+    let step = WIDTH / (durations.len().max(2) - 1) as f64;
+    let points: Vec<_> = durations
+        .iter()
+        .enumerate()
+        .filter_map(|(i, duration)| {
+            let fraction = duration.as_ref()?.as_secs_f64() / max.as_secs_f64();
+            // a pixel of margin top and bottom, so the line isn't clipped
+            let y = HEIGHT - 1.0 - fraction * (HEIGHT - 2.0);
+            Some(format!("{:.1},{y:.1}", i as f64 * step))
+        })
+        .collect();
+    format!(
+        r#"<svg width="{WIDTH}" height="{HEIGHT}"><polyline points="{}"/></svg>"#,
+        points.join(" ")
+    )
+}
+
+const STYLE: &str = "
+body { font-family: sans-serif; margin: 2em; }
+table { border-collapse: collapse; }
+th, td { padding: 0.2em 0.8em; text-align: left; border-bottom: 1px solid #ddd; }
+td.time, td.change { text-align: right; font-variant-numeric: tabular-nums; }
+.bar { background: #4a90d9; height: 0.8em; }
+.correct { color: #2a7d2a; }
+.incorrect, .known_wrong, .violates_bound, .error, .slower { color: #c0392b; font-weight: bold; }
+.unknown { color: #777; }
+polyline { fill: none; stroke: #4a90d9; stroke-width: 1.5; }
+";
+
+/// A self-contained HTML page of `run`'s results, with a time bar per part and its trend over
+/// the `history` that led up to it.
+pub fn render(run: &Run, history: &[Run], wall: Duration, cpu: Duration) -> String {
+    let longest = run
+        .results
+        .iter()
+        .filter_map(|r| r.duration)
+        .max()
+        .unwrap_or_default();
+    let recent = &history[history.len().saturating_sub(TREND_RUNS - 1)..];
+
+    let mut rows = String::new();
+    for result in &run.results {
+        let (day, part, input) = (result.day, result.part, result.input.as_deref());
+        let (answer, status) = match (&result.answer, &result.error) {
+            (Some(answer), _) if result.success => {
+                (escape(&answer.to_string()), result.verdict.name())
+            }
+            (_, Some(error)) => (escape(error), "error"),
+            _ => (String::new(), Verdict::Unknown.name()),
+        };
+        let duration = result.duration.filter(|_| result.success);
+        let time = duration.map(|d| format!("{d:.2?}")).unwrap_or_default();
+        let width = match duration {
+            Some(d) if !longest.is_zero() => d.as_secs_f64() / longest.as_secs_f64() * 100.0,
+            _ => 0.0,
+        };
+        let previous = history.last().and_then(|r| r.duration(day, part, input));
+        let change = match change(previous, duration) {
+            Some(c) if c > SLOWER => {
+                format!(r#"<span class="slower">{:+.0}%</span>"#, c * 100.0)
+            }
+            Some(c) => format!("{:+.0}%", c * 100.0),
+            None => String::new(),
+        };
+        let mut trend: Vec<_> = recent
+            .iter()
+            .map(|r| r.duration(day, part, input))
+            .collect();
+        trend.push(duration);
+
+        rows += &format!(
+            r#"<tr>
+<td>{day}</td><td>{part}</td><td>{}</td><td><code>{answer}</code></td><td class="{status}">{}</td>
+<td class="time">{time}</td><td><div class="bar" style="width: {width:.1}%"></div></td>
+<td class="change">{change}</td><td>{}</td>
+</tr>
+"#,
+            escape(input.unwrap_or("puzzle input")),
+            status.replace('_', " "),
+            sparkline(&trend),
+        );
+    }
+
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>Advent of Code 2024 run {at}</title>
+<style>{STYLE}</style>
+</head>
+<body>
+<h1>Advent of Code 2024</h1>
+<p>Run at {at}: {wall:.2?} wall, {cpu:.2?} CPU. Trends cover the last {runs} runs.</p>
+<table>
+<tr>
+<th>Day</th><th>Part</th><th>Input</th><th>Answer</th><th>Status</th>
+<th>Time</th><th style="width: 30%"></th><th>Change</th><th>Trend</th>
+</tr>
+{rows}</table>
+</body>
+</html>
+"#,
+        at = escape(&run.at),
+        runs = recent.len() + 1,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::Answer;
+    use crate::output::InputSource;
+
+    fn result(part: u32, answer: &str, millis: u64) -> PartResult {
+        PartResult {
+            day: 17,
+            part,
+            answer: Some(Answer::from(answer)),
+            duration: Some(Duration::from_millis(millis)),
+            cpu_time: None,
+            source: Some(InputSource::Cache),
+            success: true,
+            error: None,
+            verdict: Verdict::Correct,
+            input: None,
+            memo: None,
+        }
+    }
+
+    fn run(at: &str, millis: u64) -> Run {
+        Run {
+            at: at.to_string(),
+            results: vec![result(1, "4,6,3", millis), result(2, "<117440>", 2)],
+        }
+    }
+
+    #[test]
+    fn history() {
+        let path = std::env::temp_dir().join(format!("aoc-history-{}.jsonl", std::process::id()));
+        let path = path.to_str().unwrap();
+        assert!(load_history(path).unwrap().is_empty());
+
+        append_history(path, &run("2024-12-17T06:00:00+00:00", 10)).unwrap();
+        append_history(path, &run("2024-12-18T06:00:00+00:00", 12)).unwrap();
+        let history = load_history(path).unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(
+            history[1].duration(17, 1, None),
+            Some(Duration::from_millis(12))
+        );
+        assert_eq!(history[1].duration(17, 1, Some("day17/example1.txt")), None);
+
+        fs::remove_file(path).unwrap();
+    }
+    #[test]
+    fn changes() {
+        let ms = |n| Some(Duration::from_millis(n));
+        assert_eq!(change(ms(10), ms(15)), Some(0.5));
+        assert_eq!(change(ms(10), ms(5)), Some(-0.5));
+        assert_eq!(change(None, ms(5)), None);
+        assert_eq!(change(ms(0), ms(5)), None);
+    }
+    #[test]
+    fn report() {
+        let history = [run("2024-12-16", 10), run("2024-12-17", 10)];
+        let mut current = run("2024-12-18", 20);
+        current.results[1].verdict = Verdict::KnownWrong;
+
+        let html = render(
+            &current,
+            &history,
+            Duration::from_millis(22),
+            Duration::from_millis(22),
+        );
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<code>4,6,3</code>"));
+        assert!(html.contains("<code>&lt;117440&gt;</code>"));
+        assert!(html.contains(r#"<td class="known_wrong">known wrong</td>"#));
+        assert!(html.contains(r#"<div class="bar" style="width: 100.0%">"#));
+        assert!(html.contains(r#"<span class="slower">+100%</span>"#));
+        assert!(html.contains(r#"<polyline points="0.0,12.0 60.0,12.0 120.0,1.0"/>"#));
+        assert!(html.contains("Trends cover the last 3 runs"));
+        assert!(html.contains("<td>17</td><td>1</td><td>puzzle input</td>"));
+    }
+    #[test]
+    fn report_inputs() {
+        let mut example = result(1, "4,6,3", 1);
+        example.input = Some("day17/example1.txt".to_string());
+        let history = [run("2024-12-17", 10)];
+        let mut current = run("2024-12-18", 20);
+        current.results.push(example);
+
+        let html = render(&current, &history, Duration::ZERO, Duration::ZERO);
+        assert!(html.contains("<td>17</td><td>1</td><td>day17/example1.txt</td>"));
+        // the example isn't compared with the puzzle input's 10ms
+        assert!(!html.contains("-90%"));
+    }
+}
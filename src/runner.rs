@@ -0,0 +1,401 @@
+use crate::answers::{self, DayAnswers, Verdict};
+use crate::bench::{self, BenchOptions, BenchResult};
+use crate::config::Config;
+use crate::days::{self, Answer, AoCError, Day, Params};
+use crate::input::{self, get_input};
+use crate::output::{Format, InputSource, PartResult};
+use crate::{cancel, examples, memo, parallel, report};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+pub type Input = Result<(String, InputSource), AoCError>;
+
+/// Solves one part on a thread of its own, giving up after `limit`.
+pub fn run_part(
+    day: &'static dyn days::Day,
+    part: u32,
+    input: Input,
+    params: Params,
+    limit: Option<Duration>,
+) -> PartResult {
+    let mut result = PartResult {
+        day: day.day(),
+        part,
+        answer: None,
+        duration: None,
+        cpu_time: None,
+        source: None,
+        success: false,
+        error: None,
+        verdict: Verdict::Unknown,
+        input: None,
+        memo: None,
+    };
+
+    match input {
+        Ok((content, source)) => {
+            result.source = Some(source);
+
+            let start = Instant::now();
+            let solved = cancel::run(limit, move || {
+                let cpu_start = parallel::thread_cpu_time();
+                let start = Instant::now();
+                let (answer, memo) = memo::collect(|| {
+                    days::catch_panic(|| match part {
+                        1 => day.part1_with(content.trim(), &params),
+                        _ => day.part2_with(content.trim(), &params),
+                    })
+                });
+                let cpu_time = parallel::thread_cpu_time()
+                    .zip(cpu_start)
+                    .map(|(end, start)| end.saturating_sub(start));
+                (answer, start.elapsed(), cpu_time, memo)
+            });
+            let answer = match solved {
+                Ok((answer, duration, cpu_time, memo)) => {
+                    result.duration = Some(duration);
+                    result.cpu_time = cpu_time;
+                    result.memo = memo;
+                    answer
+                }
+                Err(e) => {
+                    result.duration = Some(start.elapsed());
+                    Err(e)
+                }
+            };
+
+            match answer {
+                Ok(answer) => {
+                    result.answer = Some(answer);
+                    result.success = true;
+                }
+                Err(e) => result.error = Some(e.to_string()),
+            }
+        }
+        Err(e) => result.error = Some(e.to_string()),
+    }
+
+    result
+}
+
+/// Compares a part's answer with the known answers for its day.
+pub fn verify(answers: &DayAnswers, result: &mut PartResult) {
+    let expected = answers.part(result.part);
+    result.verdict = match &result.answer {
+        Some(answer) => expected.verify(answer),
+        // the solver ran but gave up on a part we know the answer to
+        None if result.source.is_some() && expected.answer.is_some() => Verdict::Incorrect,
+        None => Verdict::Unknown,
+    };
+}
+
+/// Benchmarks one part, failing without timing it if its input can't be loaded or it fails on
+/// it.
+pub fn bench_part<F>(
+    config: &Config,
+    day: u32,
+    part: u32,
+    options: &BenchOptions,
+    run: F,
+) -> Result<BenchResult, AoCError>
+where
+    F: Fn(&str) -> Result<Answer, AoCError>,
+{
+    let (content, _) = get_input(config, day, part)?;
+    let content = content.trim();
+
+    run(content)?;
+    let (answer, stats) = bench::bench(options, || run(content).ok());
+
+    Ok(BenchResult {
+        day,
+        part,
+        answer: answer.map(|answer| answer.to_string()),
+        stats,
+    })
+}
+
+/// The parts to solve or benchmark, the parameters to solve them with and how to print them.
+#[derive(Debug, Clone, Default)]
+pub struct Selection {
+    /// Only this part, or both if `None`
+    pub part: Option<u32>,
+    pub params: Params,
+    pub format: Format,
+}
+
+impl Selection {
+    pub fn wants_part(&self, part: u32) -> bool {
+        self.part.map(|p| p == part).unwrap_or(true)
+    }
+
+    /// The parts of `day` to run, in the order they are run.
+    fn parts(&self, day: &dyn Day) -> Vec<u32> {
+        [2, 1]
+            .into_iter()
+            .filter(|&part| self.wants_part(part) && day.parts().contains(&part))
+            .collect()
+    }
+}
+
+/// What to solve each day's parts for.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Inputs {
+    /// The day's puzzle input, fetched if it isn't saved yet
+    Puzzle,
+    /// The examples in `examples/dayN/`, checked against the answers they list
+    Examples,
+    /// A file, `-` for stdin, or every `*.txt` file in a directory, each checked against a
+    /// `.toml` answers file beside it
+    Files(String),
+}
+
+/// Fails if `params` sets a parameter that none of `days` has, which would otherwise be ignored.
+pub fn check_params(days: &[&'static dyn Day], params: &Params) -> Result<(), AoCError> {
+    for name in params.names() {
+        if !days
+            .iter()
+            .any(|day| day.params().iter().any(|p| p.name == name))
+        {
+            return Err(AoCError::InvalidParameter(
+                name.to_string(),
+                "none of the days being run has it".to_string(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// A part to solve, with the name of the input when it isn't the puzzle input.
+type Job = (&'static dyn Day, u32, Option<String>, Input, Params);
+
+/// A day with one of its inputs, and the answers to check its parts against.
+type DayRun = (
+    &'static dyn Day,
+    Option<String>,
+    Result<DayAnswers, AoCError>,
+);
+
+/// The parts of `days` to solve for `inputs`, and each day and input whose results to print.
+fn jobs(
+    config: &Config,
+    days: &[&'static dyn Day],
+    selection: &Selection,
+    inputs: &Inputs,
+) -> Result<(Vec<DayRun>, Vec<Job>), AoCError> {
+    let mut runs = vec![];
+    let mut jobs = vec![];
+    match inputs {
+        Inputs::Examples => {
+            for &day in days {
+                for example in examples::load(&examples::dir(), day.day())? {
+                    example.check_params(day)?;
+                    for (i, case) in example.cases.iter().enumerate() {
+                        let name = Some(example.case_name(i));
+                        let params = case
+                            .params
+                            .iter()
+                            .fold(selection.params.clone(), |p, (k, v)| p.set(k, v));
+                        for part in selection.parts(day) {
+                            if case.answer(part).is_some() {
+                                let input = Ok((example.input.clone(), InputSource::Example));
+                                jobs.push((day, part, name.clone(), input, params.clone()));
+                            }
+                        }
+                        runs.push((day, name, Ok(case.answers())));
+                    }
+                }
+            }
+        }
+        Inputs::Files(path) => {
+            let inputs = input::read_inputs(path)?;
+            for &day in days {
+                for (name, content, source) in &inputs {
+                    let answers = match source {
+                        InputSource::File => {
+                            let answers = Path::new(name).with_extension("toml");
+                            answers::load_file(&answers.to_string_lossy())
+                        }
+                        _ => Ok(DayAnswers::default()),
+                    };
+                    for part in selection.parts(day) {
+                        let input = Ok((content.clone(), *source));
+                        let params = selection.params.clone();
+                        jobs.push((day, part, Some(name.clone()), input, params));
+                    }
+                    runs.push((day, Some(name.clone()), answers));
+                }
+            }
+        }
+        Inputs::Puzzle => {
+            // fetched up front, one at a time, so downloads still respect the request interval
+            for &day in days {
+                for part in selection.parts(day) {
+                    let input = get_input(config, day.day(), part);
+                    jobs.push((day, part, None, input, selection.params.clone()));
+                }
+                runs.push((day, None, answers::load(&config.answers_dir, day.day())));
+            }
+        }
+    }
+    Ok((runs, jobs))
+}
+
+/// Solves the parts of `days` for `inputs` on `workers` threads, then prints each day's results
+/// checked against its answers, also writing them to the HTML report at `report` if given.
+/// Returns whether any part failed or the report could not be written.
+pub fn run_days(
+    config: &Config,
+    days: &[&'static dyn Day],
+    selection: &Selection,
+    inputs: &Inputs,
+    workers: usize,
+    report: Option<&str>,
+) -> Result<bool, AoCError> {
+    let (runs, jobs) = jobs(config, days, selection, inputs)?;
+
+    let start = Instant::now();
+    let results = parallel::run_all(jobs, workers, |(day, part, name, input, params)| {
+        PartResult {
+            input: name,
+            ..run_part(day, part, input, params, config.timeout_for(day.day()))
+        }
+    });
+    let wall = start.elapsed();
+
+    let format = selection.format;
+    let emit = |text: Option<String>| {
+        if let Some(text) = text {
+            print!("{text}");
+        }
+    };
+    let mut failed = false;
+    let mut cpu = Duration::ZERO;
+    let mut results = results.into_iter().peekable();
+    let mut reported = vec![];
+
+    emit(format.header());
+    for (day, name, answers) in runs {
+        let number = day.day();
+        let answers = answers.unwrap_or_else(|e| {
+            eprintln!("{e}");
+            DayAnswers::default()
+        });
+
+        emit(format.day_start(number, name.as_deref()));
+        while let Some(mut result) = results.next_if(|r| r.day == number && r.input == name) {
+            verify(&answers, &mut result);
+            failed |= result.verdict.is_failure();
+            cpu += result.cpu_time.unwrap_or_default();
+            emit(format.part(&result));
+            reported.push(result);
+        }
+        emit(format.day_end());
+    }
+    emit(format.summary(wall, cpu));
+
+    if let Some(path) = report {
+        // only runs on the puzzle inputs with the puzzles' own parameters are comparable from one
+        // to the next
+        let record = *inputs == Inputs::Puzzle && selection.params.is_empty();
+        if let Err(e) = report::write(path, &config.history_file, record, reported, wall, cpu) {
+            eprintln!("{e}");
+            failed = true;
+        }
+    }
+    Ok(failed)
+}
+
+/// The benchmark results to compare against and where to save the new ones.
+#[derive(Debug, Clone, Default)]
+pub struct Baseline {
+    /// Compare medians against this baseline file
+    pub compare: Option<String>,
+    /// Write the results to this baseline file
+    pub save: Option<String>,
+    /// Percentage slowdown against the baseline that counts as a regression
+    pub threshold: f64,
+}
+
+/// Benchmarks the parts of `days`, returning whether any failed, regressed against the baseline
+/// or the new baseline could not be saved.
+pub fn bench_days(
+    config: &Config,
+    days: &[&'static dyn Day],
+    selection: &Selection,
+    options: &BenchOptions,
+    baseline: &Baseline,
+) -> bool {
+    let previous = match &baseline.compare {
+        Some(path) => bench::load_baseline(path).unwrap_or_else(|e| {
+            eprintln!("{e}");
+            vec![]
+        }),
+        None => vec![],
+    };
+
+    let params = &selection.params;
+    let mut results = vec![];
+    let mut failed = false;
+    if let Some(header) = selection.format.bench_header() {
+        print!("{header}");
+    }
+    for &day in days {
+        let number = day.day();
+        for part in selection.parts(day) {
+            let result = match part {
+                1 => bench_part(config, number, part, options, |l| day.part1_with(l, params)),
+                _ => bench_part(config, number, part, options, |l| day.part2_with(l, params)),
+            };
+            match result {
+                Ok(result) => {
+                    let change = bench::change_from(&previous, &result);
+                    let regression = change.is_some_and(|c| c * 100.0 > baseline.threshold);
+                    failed |= regression;
+                    print!("{}", selection.format.bench(&result, change, regression));
+                    results.push(result);
+                }
+                Err(e) => {
+                    eprintln!("Day {number} part {part}: {e}");
+                    failed = true;
+                }
+            }
+        }
+    }
+
+    if let Some(path) = &baseline.save {
+        if let Err(e) = bench::save_baseline(path, &results) {
+            eprintln!("{e}");
+            failed = true;
+        }
+    }
+    failed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+
+    #[test]
+    fn selected_parts() {
+        let day = registry::get(25).unwrap();
+        assert_eq!(Selection::default().parts(day), [1]);
+        let both = registry::get(9).unwrap();
+        assert_eq!(Selection::default().parts(both), [2, 1]);
+        let selection = Selection {
+            part: Some(1),
+            ..Selection::default()
+        };
+        assert_eq!(selection.parts(both), [1]);
+    }
+    #[test]
+    fn unknown_params() {
+        let days = [registry::get(11).unwrap(), registry::get(9).unwrap()];
+        assert!(check_params(&days, &Params::default().set("blinks", "6")).is_ok());
+        assert!(matches!(
+            check_params(&days[1..], &Params::default().set("blinks", "6")),
+            Err(AoCError::InvalidParameter(_, _))
+        ));
+    }
+}
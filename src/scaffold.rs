@@ -0,0 +1,123 @@
+use crate::days::AoCError;
+use crate::examples::{self, Example};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const TEMPLATE: &str = include_str!("day.rs.template");
+
+/// The template filled in for `day`.
+pub fn render(day: u32) -> String {
+    TEMPLATE.replace("{{day}}", &day.to_string())
+}
+
+/// Creates `dayN.rs` in `src_dir` from the template and declares it in `lib.rs` there, where the
+/// build picks it up, and writes `examples` into `examples_dir` for the example tests. Returns
+/// the files created or changed.
+pub fn new_day(
+    src_dir: &Path,
+    examples_dir: &Path,
+    day: u32,
+    examples: &[Example],
+) -> Result<Vec<PathBuf>, AoCError> {
+    let path = src_dir.join(format!("day{day}.rs"));
+    if path.exists() {
+        return Err(AoCError::NewDay(format!(
+            "{} already exists",
+            path.display()
+        )));
+    }
+
+    let lib = src_dir.join("lib.rs");
+    let declared = declare(&fs::read_to_string(&lib)?, &format!("day{day}"));
+
+    fs::write(&path, render(day))?;
+    fs::write(&lib, declared)?;
+    let mut created = vec![path, lib];
+    created.extend(examples::save(examples_dir, day, examples)?);
+    Ok(created)
+}
+
+/// `lib` with `pub mod module;` added among its other modules, in order.
+fn declare(lib: &str, module: &str) -> String {
+    fn declared(line: &str) -> Option<&str> {
+        line.strip_prefix("pub mod ")?.strip_suffix(';')
+    }
+
+    let declaration = format!("pub mod {module};");
+    let mut lines: Vec<&str> = lib.lines().collect();
+    let at = match lines
+        .iter()
+        .position(|line| declared(line).is_some_and(|m| m > module))
+    {
+        Some(at) => at,
+        None => lines
+            .iter()
+            .rposition(|line| declared(line).is_some())
+            .map_or(lines.len(), |last| last + 1),
+    };
+    lines.insert(at, &declaration);
+    lines.join("\n") + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples::Case;
+
+    #[test]
+    fn render_day() {
+        let source = render(26);
+        assert!(source.contains("    fn day(&self) -> u32 {\n        26\n    }"));
+        assert!(!source.contains("{{"));
+    }
+    #[test]
+    fn creates_module() {
+        let dir = std::env::temp_dir().join(format!("aoc-new-day-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let example = Example {
+            input: "xmul(2,4)".to_string(),
+            cases: vec![Case {
+                part1: Some("8".to_string()),
+                ..Case::default()
+            }],
+            ..Example::default()
+        };
+
+        fs::write(
+            dir.join("lib.rs"),
+            "pub mod config;\npub mod day2;\npub mod days;\n",
+        )
+        .unwrap();
+        let created = new_day(&dir, &dir.join("examples"), 3, &[example]).unwrap();
+        assert!(fs::read_to_string(&created[0])
+            .unwrap()
+            .contains("        3\n"));
+        assert_eq!(
+            fs::read_to_string(&created[1]).unwrap(),
+            "pub mod config;\npub mod day2;\npub mod day3;\npub mod days;\n"
+        );
+        assert_eq!(
+            fs::read_to_string(&created[2]).unwrap(),
+            "part1: 8\n---\nxmul(2,4)\n"
+        );
+        assert!(matches!(
+            new_day(&dir, &dir, 3, &[]),
+            Err(AoCError::NewDay(_))
+        ));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+    #[test]
+    fn declares_in_order() {
+        let lib =
+            "//! docs\n\npub mod config;\npub mod day1;\npub mod day2;\npub mod days;\n\npub use x;\n";
+        assert_eq!(
+            declare(lib, "day10"),
+            "//! docs\n\npub mod config;\npub mod day1;\npub mod day10;\npub mod day2;\npub mod days;\n\npub use x;\n"
+        );
+        assert_eq!(
+            declare("pub mod config;\n", "day1"),
+            "pub mod config;\npub mod day1;\n"
+        );
+    }
+}
@@ -0,0 +1,297 @@
+use crate::answers::{self, Hint, Verdict, WrongAnswer};
+use crate::config::Config;
+use crate::days::{Answer, AoCError};
+use crate::http;
+use crate::unlock::SystemClock;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io::ErrorKind;
+use std::sync::LazyLock;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    Wait(u64),
+    AlreadySolved,
+    Unrecognised,
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "That's the right answer!"),
+            Outcome::TooHigh => write!(f, "Wrong: too high"),
+            Outcome::TooLow => write!(f, "Wrong: too low"),
+            Outcome::Wrong => write!(f, "Wrong"),
+            Outcome::Wait(seconds) => write!(f, "Answered too recently: wait {seconds}s"),
+            Outcome::AlreadySolved => write!(f, "Already solved"),
+            Outcome::Unrecognised => write!(f, "Could not understand the response"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Submission {
+    pub time: String,
+    pub part: u32,
+    pub answer: String,
+    pub outcome: Outcome,
+}
+
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SubmissionLog {
+    #[serde(default)]
+    pub submission: Vec<Submission>,
+}
+
+impl SubmissionLog {
+    /// Any earlier submission of this answer that the server actually judged.
+    pub fn previous(&self, part: u32, answer: &str) -> Option<&Submission> {
+        self.submission.iter().find(|s| {
+            s.part == part
+                && s.answer == answer
+                && !matches!(s.outcome, Outcome::Wait(_) | Outcome::Unrecognised)
+        })
+    }
+}
+
+fn log_filename(dir: &str, day: u32) -> String {
+    format!("{dir}/day{day}.submissions.toml")
+}
+
+pub fn load_log(dir: &str, day: u32) -> Result<SubmissionLog, AoCError> {
+    let filename = log_filename(dir, day);
+    match fs::read_to_string(&filename) {
+        Ok(content) => toml::from_str(&content).map_err(|e| AoCError::AnswerFile(filename, e)),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(SubmissionLog::default()),
+        Err(e) => Err(AoCError::from(e)),
+    }
+}
+
+fn save_log(dir: &str, day: u32, log: &SubmissionLog) -> Result<(), AoCError> {
+    fs::create_dir_all(dir)?;
+    fs::write(log_filename(dir, day), toml::to_string(log).unwrap())?;
+    Ok(())
+}
+
+static WAIT_PATTERN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"you have (?:(?<m>\d+)m )?(?<s>\d+)s left to wait").unwrap());
+
+pub fn parse_response(html: &str) -> Outcome {
+    let text = html.to_lowercase();
+
+    if text.contains("that's the right answer") {
+        Outcome::Correct
+    } else if text.contains("not the right answer") {
+        if text.contains("too high") {
+            Outcome::TooHigh
+        } else if text.contains("too low") {
+            Outcome::TooLow
+        } else {
+            Outcome::Wrong
+        }
+    } else if text.contains("you gave an answer too recently") {
+        let seconds = WAIT_PATTERN
+            .captures(&text)
+            .map(|c| {
+                let minutes = c.name("m").map_or(0, |m| m.as_str().parse().unwrap());
+                let seconds: u64 = c["s"].parse().unwrap();
+                minutes * 60 + seconds
+            })
+            .unwrap_or(60);
+        Outcome::Wait(seconds)
+    } else if text.contains("did you already complete it") {
+        Outcome::AlreadySolved
+    } else {
+        Outcome::Unrecognised
+    }
+}
+
+fn post_answer(config: &Config, day: u32, part: u32, answer: &str) -> Result<Outcome, AoCError> {
+    let url = format!("{}/{}/day/{day}/answer", config.base_url, config.year);
+    let level = part.to_string();
+    let cookie = config.cookie()?;
+
+    http::throttle(config, &SystemClock)?;
+    let response = http::client(config)?
+        .post(&url)
+        .header("Cookie", cookie)
+        .form(&[("level", level.as_str()), ("answer", answer)])
+        .send()?;
+    let response = http::check_status(&url, response)?.text()?;
+
+    Ok(parse_response(&response))
+}
+
+/// Submits an answer unless the answer store or the submission log already says it is wrong,
+/// then records the outcome in both.
+pub fn submit(config: &Config, day: u32, part: u32, answer: &str) -> Result<Outcome, AoCError> {
+    let dir = &config.answers_dir;
+    let mut log = load_log(dir, day)?;
+    if let Some(previous) = log.previous(part, answer) {
+        return Err(AoCError::Submission(format!(
+            "{answer} was already submitted at {}: {}",
+            previous.time, previous.outcome
+        )));
+    }
+
+    let mut answers = answers::load(dir, day)?;
+    match answers.part(part).verify(&Answer::parse(answer)) {
+        Verdict::Correct | Verdict::Incorrect => {
+            return Err(AoCError::Submission(format!(
+                "part {part} has already been solved"
+            )))
+        }
+        Verdict::KnownWrong => {
+            return Err(AoCError::Submission(format!(
+                "{answer} is known to be wrong"
+            )))
+        }
+        Verdict::ViolatesBound => {
+            return Err(AoCError::Submission(format!(
+                "{answer} is outside the bounds of earlier wrong answers"
+            )))
+        }
+        Verdict::Unknown => {}
+    }
+
+    let outcome = post_answer(config, day, part, answer)?;
+
+    log.submission.push(Submission {
+        time: chrono::Local::now().to_rfc3339(),
+        part,
+        answer: answer.to_string(),
+        outcome,
+    });
+    save_log(dir, day, &log)?;
+
+    let known = answers.part_mut(part);
+    match outcome {
+        Outcome::Correct => known.answer = Some(answer.to_string()),
+        Outcome::TooHigh | Outcome::TooLow | Outcome::Wrong => known.wrong.push(WrongAnswer {
+            answer: answer.to_string(),
+            hint: match outcome {
+                Outcome::TooHigh => Some(Hint::TooHigh),
+                Outcome::TooLow => Some(Hint::TooLow),
+                _ => None,
+            },
+        }),
+        _ => return Ok(outcome),
+    }
+    answers::save(dir, day, &answers)?;
+
+    Ok(outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::stand_in;
+    use std::time::Duration;
+
+    const CORRECT: &str = "<main><article><p>That's the right answer!  You are one gold star closer to finding the Chief Historian.</p></article></main>";
+    const TOO_HIGH: &str = "<main><article><p>That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data; please wait one minute before trying again.</p></article></main>";
+    const TOO_LOW: &str = "<main><article><p>That's not the right answer; your answer is too low.  Please wait one minute before trying again.</p></article></main>";
+    const WRONG: &str = "<main><article><p>That's not the right answer.  If you're stuck, make sure you're using the full input data.</p></article></main>";
+    const WAIT: &str = "<main><article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 23s left to wait.</p></article></main>";
+    const SOLVED: &str = "<main><article><p>You don't seem to be solving the right level.  Did you already complete it?</p></article></main>";
+
+    fn config(name: &str, base_url: String) -> Config {
+        let dir = std::env::temp_dir().join(format!("aoc-submit-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        Config {
+            answers_dir: dir.to_str().unwrap().to_string(),
+            input_dir: dir.to_str().unwrap().to_string(),
+            base_url,
+            request_interval: Duration::ZERO,
+            session: Some("abc".to_string()),
+            ..Config::default()
+        }
+    }
+
+    #[test]
+    fn parse_responses() {
+        assert_eq!(parse_response(CORRECT), Outcome::Correct);
+        assert_eq!(parse_response(TOO_HIGH), Outcome::TooHigh);
+        assert_eq!(parse_response(TOO_LOW), Outcome::TooLow);
+        assert_eq!(parse_response(WRONG), Outcome::Wrong);
+        assert_eq!(parse_response(WAIT), Outcome::Wait(83));
+        assert_eq!(parse_response(SOLVED), Outcome::AlreadySolved);
+        assert_eq!(parse_response("<html></html>"), Outcome::Unrecognised);
+    }
+    #[test]
+    fn parse_wait_seconds_only() {
+        assert_eq!(
+            parse_response("You gave an answer too recently. You have 35s left to wait."),
+            Outcome::Wait(35)
+        );
+    }
+    #[test]
+    fn submit_posts_form_and_records_outcome() {
+        let (base_url, requests) = stand_in::server(vec![(200, TOO_HIGH), (200, CORRECT)]);
+        let config = config("posts", base_url);
+        let dir = &config.answers_dir;
+
+        let outcome = submit(&config, 9, 2, "6434671719741").unwrap();
+        assert_eq!(outcome, Outcome::TooHigh);
+
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("POST /2024/day/9/answer "));
+        assert!(request.contains("session=abc"));
+        assert!(request.ends_with("level=2&answer=6434671719741"));
+
+        // rejected locally without contacting the server
+        assert!(submit(&config, 9, 2, "6434671719741").is_err());
+        assert!(submit(&config, 9, 2, "7000000000000").is_err());
+
+        let outcome = submit(&config, 9, 2, "6418529470362").unwrap();
+        assert_eq!(outcome, Outcome::Correct);
+
+        let answers = answers::load(dir, 9).unwrap();
+        assert_eq!(answers.part2.answer, Some("6418529470362".to_string()));
+        assert_eq!(answers.part2.wrong[0].hint, Some(Hint::TooHigh));
+        assert_eq!(load_log(dir, 9).unwrap().submission.len(), 2);
+
+        assert!(submit(&config, 9, 2, "1").is_err());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+    #[test]
+    fn submit_can_retry_after_wait() {
+        let (base_url, _requests) = stand_in::server(vec![(200, WAIT), (200, TOO_LOW)]);
+        let config = config("wait", base_url);
+        let dir = &config.answers_dir;
+
+        assert_eq!(submit(&config, 1, 1, "10").unwrap(), Outcome::Wait(83));
+        assert_eq!(submit(&config, 1, 1, "10").unwrap(), Outcome::TooLow);
+        assert_eq!(
+            answers::load(dir, 1)
+                .unwrap()
+                .part1
+                .verify(&Answer::from(5)),
+            Verdict::ViolatesBound
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+    #[test]
+    fn server_errors_are_not_recorded() {
+        let (base_url, _requests) = stand_in::server(vec![(500, "Internal Server Error")]);
+        let config = config("error", base_url);
+        let dir = &config.answers_dir;
+
+        assert!(matches!(
+            submit(&config, 1, 1, "10"),
+            Err(AoCError::HttpStatus(_, 500))
+        ));
+        assert_eq!(load_log(dir, 1).unwrap(), SubmissionLog::default());
+
+        let _ = fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,177 @@
+use crate::args::DAYS;
+use chrono::{DateTime, FixedOffset, NaiveDate, TimeDelta, Utc};
+use std::time::Duration;
+
+/// Puzzles unlock at midnight US Eastern Standard Time, UTC-5; December is never in daylight
+/// saving time.
+const EASTERN_STANDARD_TIME: FixedOffset = FixedOffset::west_opt(5 * 60 * 60).unwrap();
+
+pub trait Clock {
+    fn now(&self) -> DateTime<Utc>;
+    fn sleep(&self, duration: Duration);
+}
+
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        std::thread::sleep(duration)
+    }
+}
+
+/// A clock that starts at a fixed time and only moves forward when slept on.
+#[cfg(test)]
+pub struct FakeClock {
+    now: std::cell::Cell<DateTime<Utc>>,
+}
+
+#[cfg(test)]
+impl FakeClock {
+    pub fn at(rfc3339: &str) -> FakeClock {
+        FakeClock {
+            now: std::cell::Cell::new(DateTime::parse_from_rfc3339(rfc3339).unwrap().to_utc()),
+        }
+    }
+}
+
+#[cfg(test)]
+impl Clock for FakeClock {
+    fn now(&self) -> DateTime<Utc> {
+        self.now.get()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        self.now
+            .set(self.now.get() + TimeDelta::from_std(duration).unwrap());
+    }
+}
+
+pub fn unlock_time(year: i32, day: u32) -> DateTime<Utc> {
+    NaiveDate::from_ymd_opt(year, 12, day)
+        .unwrap()
+        .and_hms_opt(0, 0, 0)
+        .unwrap()
+        .and_local_timezone(EASTERN_STANDARD_TIME)
+        .unwrap()
+        .to_utc()
+}
+
+pub fn is_unlocked(clock: &impl Clock, year: i32, day: u32) -> bool {
+    clock.now() >= unlock_time(year, day)
+}
+
+/// The first day of the event that has not unlocked yet.
+pub fn next_locked_day(clock: &impl Clock, year: i32) -> Option<u32> {
+    DAYS.clone().find(|&day| !is_unlocked(clock, year, day))
+}
+
+/// Sleeps until `day` unlocks, calling `on_tick` with the time remaining roughly every second.
+pub fn wait_for_unlock<F>(clock: &impl Clock, year: i32, day: u32, mut on_tick: F)
+where
+    F: FnMut(TimeDelta),
+{
+    let unlock = unlock_time(year, day);
+    loop {
+        let remaining = unlock - clock.now();
+        if remaining <= TimeDelta::zero() {
+            break;
+        }
+        on_tick(remaining);
+        clock.sleep(
+            remaining
+                .min(TimeDelta::seconds(1))
+                .to_std()
+                .unwrap_or(Duration::ZERO),
+        );
+    }
+}
+
+pub fn format_remaining(remaining: TimeDelta) -> String {
+    // round up so the countdown reaches 00:00:00 as the puzzle unlocks, not a second before
+    let seconds = (remaining.num_milliseconds() + 999) / 1000;
+    format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unlocks_at_midnight_est() {
+        assert_eq!(
+            unlock_time(2024, 1),
+            DateTime::parse_from_rfc3339("2024-12-01T05:00:00Z").unwrap()
+        );
+    }
+    #[test]
+    fn is_unlocked_around_release() {
+        let year = 2024;
+        assert!(!is_unlocked(
+            &FakeClock::at("2024-12-17T04:59:59Z"),
+            year,
+            17
+        ));
+        assert!(is_unlocked(
+            &FakeClock::at("2024-12-17T05:00:00Z"),
+            year,
+            17
+        ));
+        // the old EDT offset would have unlocked an hour early
+        assert!(!is_unlocked(
+            &FakeClock::at("2024-12-17T04:00:00Z"),
+            year,
+            17
+        ));
+    }
+    #[test]
+    fn next_locked() {
+        assert_eq!(
+            next_locked_day(&FakeClock::at("2024-11-30T12:00:00Z"), 2024),
+            Some(1)
+        );
+        assert_eq!(
+            next_locked_day(&FakeClock::at("2024-12-17T05:00:00Z"), 2024),
+            Some(18)
+        );
+        assert_eq!(
+            next_locked_day(&FakeClock::at("2024-12-25T05:00:00Z"), 2024),
+            None
+        );
+    }
+    #[test]
+    fn waits_until_unlock() {
+        let clock = FakeClock::at("2024-12-17T04:59:57.500Z");
+        let mut ticks = vec![];
+
+        wait_for_unlock(&clock, 2024, 17, |remaining| {
+            ticks.push(format_remaining(remaining))
+        });
+
+        assert_eq!(ticks, vec!["00:00:03", "00:00:02", "00:00:01"]);
+        assert_eq!(clock.now(), unlock_time(2024, 17));
+    }
+    #[test]
+    fn already_unlocked_does_not_wait() {
+        let clock = FakeClock::at("2024-12-18T00:00:00Z");
+        let mut ticked = false;
+        wait_for_unlock(&clock, 2024, 17, |_| ticked = true);
+        assert!(!ticked);
+    }
+    #[test]
+    fn format() {
+        assert_eq!(
+            format_remaining(TimeDelta::seconds(3 * 3600 + 62)),
+            "03:01:02"
+        );
+        assert_eq!(format_remaining(TimeDelta::milliseconds(1)), "00:00:01");
+    }
+}
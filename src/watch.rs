@@ -0,0 +1,280 @@
+use crate::config::Config;
+use crate::days::AoCError;
+use crate::output::{Format, PartResult};
+use crate::{answers, examples, input};
+use chrono::Local;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+/// When a file was last modified and how long it is, which is enough to notice an edit.
+type Stamp = (Option<SystemTime>, u64);
+
+/// Notices changes to files by polling their modification times, so it needs no platform
+/// notification API. A watched directory covers the files directly in it, including new ones.
+pub struct Watcher {
+    paths: Vec<PathBuf>,
+    stamps: BTreeMap<PathBuf, Stamp>,
+}
+
+impl Watcher {
+    pub fn new(paths: Vec<PathBuf>) -> Watcher {
+        let stamps = stamps(&paths);
+        Watcher { paths, stamps }
+    }
+
+    /// The files created, changed or deleted since the last poll.
+    pub fn poll(&mut self) -> Vec<PathBuf> {
+        let stamps = stamps(&self.paths);
+        let mut changed: Vec<_> = stamps
+            .iter()
+            .filter(|&(file, stamp)| self.stamps.get(file) != Some(stamp))
+            .map(|(file, _)| file.clone())
+            .collect();
+        changed.extend(
+            self.stamps
+                .keys()
+                .filter(|file| !stamps.contains_key(*file))
+                .cloned(),
+        );
+        changed.sort();
+        self.stamps = stamps;
+        changed
+    }
+}
+
+fn stamps(paths: &[PathBuf]) -> BTreeMap<PathBuf, Stamp> {
+    let mut stamps = BTreeMap::new();
+    for path in paths {
+        let files = match fs::read_dir(path) {
+            Ok(entries) => entries
+                .filter_map(|entry| Some(entry.ok()?.path()))
+                .collect(),
+            Err(_) => vec![path.clone()],
+        };
+        for file in files {
+            match fs::metadata(&file) {
+                Ok(metadata) if metadata.is_file() => {
+                    stamps.insert(file, (metadata.modified().ok(), metadata.len()));
+                }
+                _ => {}
+            }
+        }
+    }
+    stamps
+}
+
+/// Rebuilds the crate in `manifest_dir` with the same profile as this binary, leaving cargo to
+/// show any compile errors. Returns whether it built.
+pub fn build(manifest_dir: &Path) -> Result<bool, AoCError> {
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let mut command = Command::new(cargo);
+    command.current_dir(manifest_dir).args(["build", "--quiet"]);
+    if !cfg!(debug_assertions) {
+        command.arg("--release");
+    }
+    Ok(command.status()?.success())
+}
+
+/// Runs `exe` with `args` and JSON output, returning the results it reports. Running the freshly
+/// built binary rather than solving in this process is what lets source changes take effect.
+pub fn run(exe: &Path, args: &[String]) -> Result<Vec<PartResult>, AoCError> {
+    let output = Command::new(exe)
+        .args(args)
+        .args(["--format", "json"])
+        .stderr(Stdio::inherit())
+        .output()?;
+    parse_results(&String::from_utf8_lossy(&output.stdout))
+}
+
+fn parse_results(output: &str) -> Result<Vec<PartResult>, AoCError> {
+    output
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            serde_json::from_str(line).map_err(|e| {
+                AoCError::Io(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("unexpected output '{line}': {e}"),
+                ))
+            })
+        })
+        .collect()
+}
+
+/// What a result shows: its answer, or its error.
+pub fn shown(result: &PartResult) -> String {
+    match (&result.answer, &result.error) {
+        (Some(answer), _) if result.success => answer.to_string(),
+        (_, Some(error)) => format!("error: {error}"),
+        _ => String::new(),
+    }
+}
+
+/// How a result compares with the previous run's, e.g. ` (was 41)`; empty on the first run.
+pub fn change(previous: Option<&str>, current: &str) -> String {
+    match previous {
+        None => String::new(),
+        Some(previous) if previous == current => " (unchanged)".to_string(),
+        Some(previous) => format!(" (was {previous})"),
+    }
+}
+
+/// Polls the files that affect `day` and re-runs it whenever one changes, rebuilding first if any
+/// source did or the last build failed. Each run is given `options` besides the day, the config's
+/// timeout and the format. Only returns if it cannot start.
+pub fn watch(
+    config: &Config,
+    day: u32,
+    options: &[String],
+    interval: Duration,
+) -> Result<(), AoCError> {
+    let exe = std::env::current_exe()?;
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    // the whole of src, as days share modules like the grid and parsing
+    let source = manifest_dir.join("src");
+    let files = vec![
+        source.clone(),
+        PathBuf::from(input::input_filename(config, day, 1)),
+        PathBuf::from(answers::answers_filename(&config.answers_dir, day)),
+        examples::dir().join(format!("day{day}")),
+    ];
+    for file in &files {
+        println!("Watching {}", file.display());
+    }
+    let mut watcher = Watcher::new(files);
+
+    let mut args = vec!["--day".to_string(), day.to_string()];
+    args.extend_from_slice(options);
+    if let Some(timeout) = config.timeout {
+        args.extend([
+            "--timeout".to_string(),
+            format!("{}ms", timeout.as_millis()),
+        ]);
+    }
+
+    let mut previous = BTreeMap::new();
+    // the binary may be older than the source even on the first run
+    let mut changed = vec![source.clone()];
+    // until a build succeeds, running would report the old binary's answers as current
+    let mut needs_build = true;
+    loop {
+        if !changed.is_empty() {
+            let names: Vec<_> = changed
+                .iter()
+                .map(|file| file.display().to_string())
+                .collect();
+            println!(
+                "\n[{}] {}",
+                Local::now().format("%H:%M:%S"),
+                names.join(", ")
+            );
+            needs_build |= changed.iter().any(|file| file.starts_with(&source));
+            match needs_build.then(|| build(manifest_dir)) {
+                Some(Ok(false)) => println!("Build failed"),
+                Some(Err(e)) => eprintln!("{e}"),
+                _ => {
+                    needs_build = false;
+                    run_day(&exe, &args, day, &mut previous)
+                }
+            }
+        }
+        thread::sleep(interval);
+        changed = watcher.poll();
+    }
+}
+
+/// Runs the examples and then the puzzle input for `day` through the freshly built binary,
+/// showing how the puzzle answers differ from `previous`, which it updates.
+fn run_day(exe: &Path, args: &[String], day: u32, previous: &mut BTreeMap<u32, String>) {
+    let examples_args = [args, &["--examples".to_string()]].concat();
+    let examples = match run(exe, &examples_args) {
+        Ok(examples) => examples,
+        Err(e) => {
+            eprintln!("{e}");
+            return;
+        }
+    };
+    for result in &examples {
+        if let Some(text) = Format::Text.part(result) {
+            println!("{}:{text}", result.input.as_deref().unwrap_or_default());
+        }
+    }
+    if examples
+        .iter()
+        .any(|result| !result.success || result.verdict.is_failure())
+    {
+        println!("Examples failed, so not running the puzzle input");
+        return;
+    }
+
+    let results = match run(exe, args) {
+        Ok(results) => results,
+        Err(e) => {
+            eprintln!("{e}");
+            return;
+        }
+    };
+    for result in results {
+        let shown = shown(&result);
+        let change = change(previous.get(&result.part).map(String::as_str), &shown);
+        if let Some(text) = Format::Text.part(&result) {
+            println!("Day {day}:{text}{change}");
+        }
+        previous.insert(result.part, shown);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::answers::Verdict;
+    use crate::days::Answer;
+
+    #[test]
+    fn polls_for_changes() {
+        let dir = std::env::temp_dir().join(format!("aoc-watch-{}", std::process::id()));
+        let examples = dir.join("examples");
+        fs::create_dir_all(&examples).unwrap();
+        let input = dir.join("day1.txt");
+        fs::write(&input, "3   4").unwrap();
+
+        let mut watcher = Watcher::new(vec![input.clone(), examples.clone()]);
+        assert!(watcher.poll().is_empty());
+
+        fs::write(&input, "3   4\n4   3").unwrap();
+        let example = examples.join("example1.txt");
+        fs::write(&example, "part1: 11\n---\n3   4").unwrap();
+        assert_eq!(watcher.poll(), [input.clone(), example.clone()]);
+        assert!(watcher.poll().is_empty());
+
+        fs::remove_file(&example).unwrap();
+        assert_eq!(watcher.poll(), [example]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+    #[test]
+    fn reads_results() {
+        let output = r#"{"day":1,"part":2,"answer":31,"duration_ns":1500,"source":"example","success":true,"error":null,"verdict":"correct","input":"day1/example1.txt"}
+{"day":1,"part":1,"answer":null,"duration_ns":null,"source":null,"success":false,"error":"an IO error occurred","verdict":"unknown"}
+"#;
+        let results = parse_results(output).unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].answer, Some(Answer::from(31)));
+        assert_eq!(results[0].verdict, Verdict::Correct);
+        assert_eq!(results[0].input.as_deref(), Some("day1/example1.txt"));
+        assert_eq!(shown(&results[0]), "31");
+        assert_eq!(shown(&results[1]), "error: an IO error occurred");
+        assert!(parse_results("Day 1:\tPart 1:\t11").is_err());
+    }
+    #[test]
+    fn changes() {
+        assert_eq!(change(None, "31"), "");
+        assert_eq!(change(Some("31"), "31"), " (unchanged)");
+        assert_eq!(change(Some("30"), "31"), " (was 30)");
+    }
+}